@@ -0,0 +1,34 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Generates `embedded_inputs.rs`, which lists every day number with its puzzle input.
+/// The inputs are only compiled into the binary with the `embed-inputs` feature.
+fn main() {
+    let inputs = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/days/inputs");
+    println!("cargo:rerun-if-changed={}", inputs.display());
+
+    let mut entries = String::new();
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let mut files: Vec<_> = fs::read_dir(&inputs)
+            .unwrap()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        files.sort();
+        for path in files {
+            if let Some(day) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u8>().ok())
+            {
+                writeln!(entries, "    ({day}, include_str!({:?})),", path).unwrap();
+            }
+        }
+    }
+
+    let code = format!("pub const EMBEDDED_INPUTS: &[(u8, &str)] = &[\n{entries}];\n");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out, code).unwrap();
+}
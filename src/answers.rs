@@ -0,0 +1,95 @@
+use crate::days::{Day, Part};
+use color_eyre::eyre::{eyre, WrapErr};
+use std::collections::HashMap;
+use std::fs;
+use toml::{Table, Value};
+
+/// File holding the accepted answers for the puzzle inputs, keyed by day and part.
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/days/answers.toml");
+
+/// Accepted answers, in the same textual form as a displayed [`crate::days::Answer`].
+pub struct Answers {
+    answers: HashMap<(u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load() -> color_eyre::Result<Self> {
+        let content = fs::read_to_string(ANSWERS_PATH)
+            .wrap_err_with(|| format!("Failed to read answers {ANSWERS_PATH}"))?;
+        content
+            .parse()
+            .wrap_err_with(|| format!("Invalid answers file {ANSWERS_PATH}"))
+    }
+
+    pub fn get(&self, day: &Day, part: Part) -> Option<&String> {
+        self.answers.get(&(day.number, part))
+    }
+
+    /// Whether any part of `day` has an accepted answer.
+    pub fn has_day(&self, day: &Day) -> bool {
+        Part::BOTH.iter().any(|&part| self.get(day, part).is_some())
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = HashMap::new();
+        for (day, parts) in s.parse::<Table>()? {
+            let number: u8 = day.parse().wrap_err_with(|| format!("Invalid day {day}"))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| eyre!("Day {day} must be a table of parts"))?;
+            for (part, value) in parts {
+                let part = match part.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => return Err(eyre!("Invalid part {part} for day {day}")),
+                };
+                let expected = match value {
+                    Value::Integer(n) => n.to_string(),
+                    // Multi-line screens are written with the closing quotes on their own line.
+                    Value::String(s) => s.trim_end_matches('\n').to_string(),
+                    _ => return Err(eyre!("Unsupported answer {value} for day {day}")),
+                };
+                answers.insert((number, part), expected);
+            }
+        }
+        Ok(Answers { answers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+
+    const ANSWERS: &str = concat!(
+        "[01]\n",
+        "part1 = 24000\n",
+        "[05]\n",
+        "part2 = \"MCD\"\n",
+        "[10]\n",
+        "part2 = '''\n",
+        "##..\n",
+        "#..#\n",
+        "'''\n",
+    );
+
+    #[test]
+    fn parse_answers() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.get(&DAYS[0], Part::One).unwrap(), "24000");
+        assert_eq!(answers.get(&DAYS[0], Part::Two), None);
+        assert_eq!(answers.get(&DAYS[4], Part::Two).unwrap(), "MCD");
+        assert_eq!(answers.get(&DAYS[9], Part::Two).unwrap(), "##..\n#..#");
+        assert!(answers.has_day(&DAYS[4]));
+        assert!(!answers.has_day(&DAYS[1]));
+    }
+
+    #[test]
+    fn reject_unknown_part() {
+        assert!("[01]\npart3 = 1\n".parse::<Answers>().is_err())
+    }
+}
@@ -0,0 +1,210 @@
+use crate::days::{Day, ParseError, Part};
+use serde_json::json;
+use std::fmt;
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+/// How long to run a phase before sampling, to warm caches and estimate a single run.
+const WARMUP: Duration = Duration::from_millis(100);
+/// How long to sample a phase for when no run count is given.
+const BUDGET: Duration = Duration::from_secs(1);
+const MIN_RUNS: usize = 5;
+const MAX_RUNS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Solve(part) => f.pad(&format!("part {part}")),
+        }
+    }
+}
+
+/// Timing statistics over the samples of one phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarizes `samples`, which must not be empty; [`measure`] always takes at least one.
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let runs = samples.len();
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+        Stats {
+            runs,
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / runs as u32,
+            p95: samples[(runs * 95).div_ceil(100) - 1],
+        }
+    }
+}
+
+pub struct Measurement {
+    pub day: &'static Day,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Warms up `f`, then times it `runs` times, or as often as fits in [`BUDGET`].
+fn measure(runs: Option<NonZeroUsize>, mut f: impl FnMut()) -> Stats {
+    let start = Instant::now();
+    let mut warmups = 0;
+    while warmups == 0 || start.elapsed() < WARMUP {
+        f();
+        warmups += 1;
+    }
+    let estimate = start.elapsed() / warmups;
+    let runs = runs.map_or_else(
+        || {
+            let fitting = BUDGET.as_nanos() / estimate.as_nanos().max(1);
+            fitting.clamp(MIN_RUNS as u128, MAX_RUNS as u128) as usize
+        },
+        NonZeroUsize::get,
+    );
+
+    let samples = (0..runs)
+        .map(|_| {
+            let before = Instant::now();
+            f();
+            before.elapsed()
+        })
+        .collect();
+    Stats::new(samples)
+}
+
+/// Benchmarks parsing the input and solving each part on the parsed model separately.
+pub fn bench(
+    day: &'static Day,
+    input: &str,
+    runs: Option<NonZeroUsize>,
+) -> Result<Vec<Measurement>, ParseError> {
+    let model = day.solution.parse(input)?;
+    let mut measurements = vec![Measurement {
+        day,
+        phase: Phase::Parse,
+        stats: measure(runs, || {
+            let _ = black_box(day.solution.parse(black_box(input)));
+        }),
+    }];
+    for part in Part::BOTH {
+        measurements.push(Measurement {
+            day,
+            phase: Phase::Solve(part),
+            stats: measure(runs, || {
+                black_box(day.solution.solve_parsed(part, black_box(model.as_ref())));
+            }),
+        });
+    }
+    Ok(measurements)
+}
+
+pub fn print_table(measurements: &[Measurement]) {
+    println!(
+        "{:>3}  {:<6}  {:>6}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "Day", "Phase", "Runs", "Min", "Median", "Mean", "p95"
+    );
+    for m in measurements {
+        println!(
+            "{:>3}  {:<6}  {:>6}  {:>10}  {:>10}  {:>10}  {:>10}",
+            format!("{:02}", m.day.number),
+            m.phase,
+            m.stats.runs,
+            format!("{:.2?}", m.stats.min),
+            format!("{:.2?}", m.stats.median),
+            format!("{:.2?}", m.stats.mean),
+            format!("{:.2?}", m.stats.p95),
+        );
+    }
+}
+
+pub fn print_csv(measurements: &[Measurement]) {
+    println!("day,phase,runs,min_ns,median_ns,mean_ns,p95_ns");
+    for m in measurements {
+        println!(
+            "{},{},{},{},{},{},{}",
+            m.day.number,
+            m.phase,
+            m.stats.runs,
+            m.stats.min.as_nanos(),
+            m.stats.median.as_nanos(),
+            m.stats.mean.as_nanos(),
+            m.stats.p95.as_nanos(),
+        );
+    }
+}
+
+pub fn print_json(measurements: &[Measurement]) {
+    for m in measurements {
+        let (phase, part) = match m.phase {
+            Phase::Parse => ("parse", None),
+            Phase::Solve(part) => ("solve", Some(part.number())),
+        };
+        println!(
+            "{}",
+            json!({
+                "day": m.day.number,
+                "phase": phase,
+                "part": part,
+                "runs": m.stats.runs,
+                "min_ns": m.stats.min.as_nanos() as u64,
+                "median_ns": m.stats.median.as_nanos() as u64,
+                "mean_ns": m.stats.mean.as_nanos() as u64,
+                "p95_ns": m.stats.p95.as_nanos() as u64,
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn stats_odd() {
+        let stats = Stats::new(millis(&[5, 1, 3]));
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn stats_p95() {
+        let stats = Stats::new(millis(&(1..=100).rev().collect::<Vec<_>>()));
+        assert_eq!(stats.median, Duration::from_micros(50_500));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn single_run() {
+        let stats = measure(NonZeroUsize::new(1), || {});
+        assert_eq!(stats.runs, 1);
+        assert_eq!(stats.min, stats.p95);
+    }
+}
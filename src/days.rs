@@ -1,13 +1,322 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
+use std::any::Any;
+use std::fmt;
+
+macro_rules! days {
+    ($($module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        /// Every registered day, in puzzle order.
+        pub const DAYS: &[Day] = &[$($module::DAY),*];
+
+        #[cfg(test)]
+        mod samples {
+            $(
+                #[test]
+                fn $module() {
+                    let day = &super::$module::DAY;
+                    for sample in day.samples {
+                        let solution = sample.solver(day);
+                        let model = solution.parse(sample.input).unwrap();
+                        assert_eq!(
+                            solution.solve_parsed(sample.part, model.as_ref()).to_string(),
+                            sample.expected,
+                            "part {}",
+                            sample.part
+                        );
+                    }
+                }
+            )*
+        }
+    };
+}
+
+days!(
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25
+);
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// Multi-line output that has to be read by a human, e.g. the CRT of day 10.
+    Screen(String),
+    /// The input is valid but has no answer, e.g. the destination is unreachable.
+    NoSolution(String),
+}
+
+impl Answer {
+    /// Name of the variant, for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Number(_) => "number",
+            Answer::Text(_) => "text",
+            Answer::Screen(_) => "screen",
+            Answer::NoSolution(_) => "no_solution",
+        }
+    }
+
+    /// The answer in `value`, or [`Answer::NoSolution`] with `reason` if there is none.
+    pub fn or_no_solution<T: Into<Answer>>(value: Option<T>, reason: &str) -> Answer {
+        value.map_or_else(|| Answer::NoSolution(reason.into()), Into::into)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) | Answer::Screen(s) => write!(f, "{s}"),
+            Answer::NoSolution(reason) => write!(f, "no solution ({reason})"),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+impl std::str::FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part {s}. Possible options are: 1, 2.")),
+        }
+    }
+}
+
+/// Malformed puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input.
+    pub line: usize,
+    /// 1-based column within the line, counting characters rather than bytes.
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error at `text`, which has to be a slice of `line`.
+    pub fn within(line_number: usize, line: &str, text: &str, message: impl Into<String>) -> Self {
+        let offset = text.as_ptr() as usize - line.as_ptr() as usize;
+        let column = line[..offset].chars().count() + 1;
+        ParseError::new(line_number, column, text, message)
+    }
+
+    /// An error at the character of `line` that spans byte `offset`, or at the end of the
+    /// line if `offset` is past it.
+    pub fn at(line_number: usize, line: &str, offset: usize, message: impl Into<String>) -> Self {
+        let start = (0..=offset.min(line.len()))
+            .rev()
+            .find(|&i| line.is_char_boundary(i))
+            .unwrap_or_default();
+        let end = line[start..]
+            .chars()
+            .next()
+            .map_or(start, |c| start + c.len_utf8());
+        ParseError::within(line_number, line, &line[start..end], message)
+    }
+
+    /// An error at the end of `input`, for input that stops short of something required.
+    pub fn end_of(input: &str, message: impl Into<String>) -> Self {
+        ParseError::new(input.lines().count() + 1, 1, "", message)
+    }
+
+    /// Moves the error down by `lines`, for errors found in a block that doesn't start
+    /// on the first line of the input.
+    pub fn shifted(self, lines: usize) -> Self {
+        ParseError {
+            line: self.line + lines,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} at {:?}",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A day's solution: parses the puzzle input into a model that both parts solve.
+pub trait Solution {
+    type Model: 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError>;
+
+    fn part1(&self, model: &Self::Model) -> Answer;
+
+    fn part2(&self, model: &Self::Model) -> Answer;
+}
+
+/// Object safe form of [`Solution`], so days with different models can share a registry.
+pub trait Solver {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    /// Solves a part for a model returned by [`Solver::parse`] of the same solver.
+    fn solve_parsed(&self, part: Part, model: &dyn Any) -> Answer;
+}
+
+impl<S: Solution> Solver for S {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve_parsed(&self, part: Part, model: &dyn Any) -> Answer {
+        let model = model
+            .downcast_ref::<S::Model>()
+            .expect("model was parsed by another day");
+        match part {
+            Part::One => self.part1(model),
+            Part::Two => self.part2(model),
+        }
+    }
+}
+
+/// An example input from the puzzle text together with its expected answer.
+pub struct Sample {
+    pub part: Part,
+    pub input: &'static str,
+    pub expected: &'static str,
+    /// Solves this sample instead of the day's solution, for puzzles whose parameters differ
+    /// between the example and the real input.
+    pub solution: Option<&'static dyn Solver>,
+}
+
+impl Sample {
+    pub const fn new(part: Part, input: &'static str, expected: &'static str) -> Self {
+        Sample {
+            part,
+            input,
+            expected,
+            solution: None,
+        }
+    }
+
+    pub const fn with_solution(self, solution: &'static dyn Solver) -> Self {
+        Sample {
+            solution: Some(solution),
+            ..self
+        }
+    }
+
+    /// The solution that solves this sample of `day`.
+    pub fn solver(&self, day: &Day) -> &'static dyn Solver {
+        self.solution.unwrap_or(day.solution)
+    }
+}
+
+/// A puzzle day as registered by its module.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub solution: &'static dyn Solver,
+    pub samples: &'static [Sample],
+}
+
+impl Day {
+    pub fn find(number: u8) -> Option<&'static Day> {
+        DAYS.iter().find(|d| d.number == number)
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {:02}: {}", self.number, self.title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_count_characters() {
+        let line = "é=x";
+        let error = ParseError::within(1, line, &line[3..], "");
+        assert_eq!((error.column, error.text.as_str()), (3, "x"));
+        // Inside the two bytes of é.
+        let error = ParseError::at(1, line, 1, "");
+        assert_eq!((error.column, error.text.as_str()), (1, "é"));
+        let error = ParseError::at(1, line, line.len(), "");
+        assert_eq!((error.column, error.text.as_str()), (4, ""));
+    }
+}
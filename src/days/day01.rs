@@ -1,9 +1,32 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use itertools::Itertools;
 
-fn read_calories(input_file: &str) -> Vec<i32> {
-    input_file
+pub const DAY: Day = Day {
+    number: 1,
+    title: "Calorie Counting",
+    solution: &Day01,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/01.txt"), "24000"),
+        Sample::new(Part::Two, include_str!("samples/01.txt"), "45000"),
+    ],
+};
+
+pub struct Day01;
+
+fn read_calories(input_file: &str) -> Result<Vec<i32>, ParseError> {
+    let values = input_file
         .lines()
-        .map(|v| v.parse::<i32>().ok())
+        .enumerate()
+        .map(|(i, v)| match v {
+            "" => Ok(None),
+            _ => v
+                .parse::<i32>()
+                .map(Some)
+                .map_err(|_| ParseError::new(i + 1, 1, v, "expected a calorie count")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(values
+        .into_iter()
         .batching(|it| {
             let mut sum = None;
             while let Some(Some(v)) = it.next() {
@@ -11,23 +34,48 @@ fn read_calories(input_file: &str) -> Vec<i32> {
             }
             sum
         })
-        .collect()
+        .collect())
 }
 
-fn part1(input_file: &str) {
-    let calories = read_calories(input_file);
-    let answer = calories.iter().max().unwrap();
-    println!("Part1: {answer:?}");
+/// The most calories carried by a single elf.
+pub fn part1(calories: &[i32]) -> Option<i32> {
+    calories.iter().max().copied()
 }
 
-fn part2(input_file: &str) {
-    let mut calories = read_calories(input_file);
+/// The calories carried by the three elves carrying the most.
+pub fn part2(calories: &[i32]) -> Option<i32> {
+    if calories.is_empty() {
+        return None;
+    }
+    let mut calories = calories.to_vec();
     calories.sort_by(|a, b| b.cmp(a));
-    let answer = calories.iter().take(3).sum::<i32>();
-    println!("Part2: {answer:?}");
+    Some(calories.iter().take(3).sum::<i32>())
 }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+impl Solution for Day01 {
+    type Model = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_calories(input)
+    }
+
+    fn part1(&self, calories: &Self::Model) -> Answer {
+        Answer::or_no_solution(part1(calories), "no elves")
+    }
+
+    fn part2(&self, calories: &Self::Model) -> Answer {
+        Answer::or_no_solution(part2(calories), "no elves")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_elves() {
+        let calories = read_calories("").unwrap();
+        assert_eq!(part1(&calories), None);
+        assert_eq!(part2(&calories), None);
+    }
 }
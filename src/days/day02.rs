@@ -1,8 +1,21 @@
 use crate::days::day02::Play::{Paper, Rock, Scissors};
 use crate::days::day02::RoundResult::{Draw, Lose, Win};
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 
-#[derive(PartialEq, Eq, Copy, Clone)]
-enum Play {
+pub const DAY: Day = Day {
+    number: 2,
+    title: "Rock Paper Scissors",
+    solution: &Day02,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/02.txt"), "15"),
+        Sample::new(Part::Two, include_str!("samples/02.txt"), "12"),
+    ],
+};
+
+pub struct Day02;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Play {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
@@ -15,6 +28,15 @@ enum RoundResult {
     Win,
 }
 
+/// The second column of the strategy guide, read as a response in part 1 and as an outcome
+/// in part 2.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
 struct PlayRound {
     opponent: Play,
     response: Option<Play>,
@@ -83,22 +105,46 @@ impl PlayRound {
     }
 }
 
-fn part1(input_file: &str) {
-    let rounds: Vec<PlayRound> = input_file
+fn read_strategy_guide(input_file: &str) -> Result<Vec<(Play, Column)>, ParseError> {
+    input_file
         .lines()
-        .map(|val| {
+        .enumerate()
+        .map(|(i, val)| {
             let line_split: Vec<&str> = val.split(' ').collect();
-            let opponent: Play = match line_split[0] {
-                "A" => Rock,
-                "B" => Paper,
-                "C" => Scissors,
-                _ => Scissors,
-            };
-            let response: Play = match line_split[1] {
-                "X" => Rock,
-                "Y" => Paper,
-                "Z" => Scissors,
-                _ => Scissors,
+            match line_split[..] {
+                [first, second] => {
+                    let opponent = match first {
+                        "A" => Rock,
+                        "B" => Paper,
+                        "C" => Scissors,
+                        _ => return Err(ParseError::new(i + 1, 1, first, "expected A, B or C")),
+                    };
+                    let column = match second {
+                        "X" => Column::X,
+                        "Y" => Column::Y,
+                        "Z" => Column::Z,
+                        _ => {
+                            let message = "expected X, Y or Z";
+                            return Err(ParseError::within(i + 1, val, second, message));
+                        }
+                    };
+                    Ok((opponent, column))
+                }
+                _ => Err(ParseError::new(i + 1, 1, val, "expected two columns")),
+            }
+        })
+        .collect()
+}
+
+/// The total score when the second column is the response to play.
+pub fn part1(guide: &[(Play, Column)]) -> i32 {
+    let rounds: Vec<PlayRound> = guide
+        .iter()
+        .map(|&(opponent, column)| {
+            let response = match column {
+                Column::X => Rock,
+                Column::Y => Paper,
+                Column::Z => Scissors,
             };
             PlayRound {
                 opponent,
@@ -112,25 +158,18 @@ fn part1(input_file: &str) {
     for mut round in rounds {
         total_score += round.round_score()
     }
-    println!("Part 1: {}", total_score);
+    total_score
 }
 
-fn part2(input_file: &str) {
-    let rounds: Vec<PlayRound> = input_file
-        .lines()
-        .map(|val| {
-            let line_split: Vec<&str> = val.split(' ').collect();
-            let opponent: Play = match line_split[0] {
-                "A" => Rock,
-                "B" => Paper,
-                "C" => Scissors,
-                _ => Scissors,
-            };
-            let round_result: RoundResult = match line_split[1] {
-                "X" => Lose,
-                "Y" => Draw,
-                "Z" => Win,
-                _ => Draw,
+/// The total score when the second column is the outcome to reach.
+pub fn part2(guide: &[(Play, Column)]) -> i32 {
+    let rounds: Vec<PlayRound> = guide
+        .iter()
+        .map(|&(opponent, column)| {
+            let round_result = match column {
+                Column::X => Lose,
+                Column::Y => Draw,
+                Column::Z => Win,
             };
             PlayRound {
                 opponent,
@@ -144,12 +183,23 @@ fn part2(input_file: &str) {
     for mut round in rounds {
         total_score += round.round_score()
     }
-    println!("Part 2: {}", total_score);
+    total_score
 }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+impl Solution for Day02 {
+    type Model = Vec<(Play, Column)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_strategy_guide(input)
+    }
+
+    fn part1(&self, guide: &Self::Model) -> Answer {
+        part1(guide).into()
+    }
+
+    fn part2(&self, guide: &Self::Model) -> Answer {
+        part2(guide).into()
+    }
 }
 
 #[cfg(test)]
@@ -188,4 +238,12 @@ mod tests {
             .round_score()
         )
     }
+
+    #[test]
+    fn strategy_guide() {
+        let guide = read_strategy_guide("A Y\nC X\n").unwrap();
+        assert_eq!(guide, [(Rock, Column::Y), (Scissors, Column::X)]);
+        let error = read_strategy_guide("A Y\nB W\n").err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "W"));
+    }
 }
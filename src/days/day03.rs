@@ -1,13 +1,26 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use std::collections::HashSet;
 
+pub const DAY: Day = Day {
+    number: 3,
+    title: "Rucksack Reorganization",
+    solution: &Day03,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/03.txt"), "157"),
+        Sample::new(Part::Two, include_str!("samples/03.txt"), "70"),
+    ],
+};
+
+pub struct Day03;
+
 #[derive(PartialEq, Eq, Clone)]
-struct RuckSack {
+pub struct RuckSack {
     items: Vec<char>,
     priority: i32,
 }
 
 impl RuckSack {
-    fn new(rucksack: String) -> RuckSack {
+    pub fn new(rucksack: String) -> RuckSack {
         let items = rucksack.chars().collect();
         let mut first: Vec<char> = rucksack.chars().collect();
         let second: Vec<char> = first.split_off(first.len() / 2);
@@ -22,7 +35,7 @@ impl RuckSack {
         RuckSack { items, priority }
     }
 
-    fn char_to_priority(c: char) -> i32 {
+    pub fn char_to_priority(c: char) -> i32 {
         let ascii_code = c as i32;
         // 65-90 = A-Z
         if (65..=90).contains(&ascii_code) {
@@ -33,27 +46,37 @@ impl RuckSack {
     }
 }
 
-fn part1(payload: &str) {
-    let total_priority = payload
+fn read_rucksacks(payload: &str) -> Result<Vec<RuckSack>, ParseError> {
+    payload
         .lines()
-        .map(|l| RuckSack::new(String::from(l)))
+        .enumerate()
+        .map(|(i, l)| match l.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            Some((offset, _)) => {
+                Err(ParseError::at(i + 1, l, offset, "expected an item from a-z or A-Z"))
+            }
+            None => Ok(RuckSack::new(String::from(l))),
+        })
+        .collect()
+}
+
+/// The summed priorities of the items found in both compartments of a rucksack.
+pub fn part1(rucksacks: &[RuckSack]) -> i32 {
+    rucksacks
+        .iter()
         .map(|r| r.priority)
         .reduce(|a, b| a + b)
-        .unwrap_or(0);
-    println!("Part 1: {}", total_priority);
+        .unwrap_or(0)
 }
 
-fn part2(payload: &str) {
-    let rucksacks: Vec<RuckSack> = payload
-        .lines()
-        .map(|l| RuckSack::new(String::from(l)))
-        .collect();
-
+/// The summed priorities of the badges shared by each group of three rucksacks, or `None`
+/// if the rucksacks can't be split into such groups.
+pub fn part2(rucksacks: &[RuckSack]) -> Option<i32> {
+    if !rucksacks.len().is_multiple_of(3) {
+        return None;
+    }
     let mut priority_sum = 0;
-    for group_index in (0..rucksacks.len()).step_by(3) {
-        let rucksack_1 = &rucksacks[group_index];
-        let rucksack_2 = &rucksacks[group_index + 1];
-        let rucksack_3 = &rucksacks[group_index + 2];
+    for group in rucksacks.chunks_exact(3) {
+        let (rucksack_1, rucksack_2, rucksack_3) = (&group[0], &group[1], &group[2]);
 
         for c in rucksack_1.items.iter() {
             if rucksack_2.items.contains(c) && rucksack_3.items.contains(c) {
@@ -62,12 +85,23 @@ fn part2(payload: &str) {
             }
         }
     }
-    println!("Part 2: {}", priority_sum);
+    Some(priority_sum)
 }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+impl Solution for Day03 {
+    type Model = Vec<RuckSack>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_rucksacks(input)
+    }
+
+    fn part1(&self, rucksacks: &Self::Model) -> Answer {
+        part1(rucksacks).into()
+    }
+
+    fn part2(&self, rucksacks: &Self::Model) -> Answer {
+        Answer::or_no_solution(part2(rucksacks), "rucksacks do not form groups of three")
+    }
 }
 
 #[cfg(test)]
@@ -92,4 +126,14 @@ mod tests {
         let ruck_sack = RuckSack::new(input);
         assert_eq!(ruck_sack.priority, expected)
     }
+
+    #[test]
+    fn incomplete_group() {
+        let rucksacks = read_rucksacks("aa\nbb\ncc\ndd\n").unwrap();
+        assert_eq!(Day03.part1(&rucksacks), Answer::Number(1 + 2 + 3 + 4));
+        assert_eq!(
+            Day03.part2(&rucksacks),
+            Answer::NoSolution("rucksacks do not form groups of three".into())
+        );
+    }
 }
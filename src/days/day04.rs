@@ -1,60 +1,90 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use std::ops::RangeInclusive;
 
+pub const DAY: Day = Day {
+    number: 4,
+    title: "Camp Cleanup",
+    solution: &Day04,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/04.txt"), "2"),
+        Sample::new(Part::Two, include_str!("samples/04.txt"), "4"),
+    ],
+};
+
+pub struct Day04;
+
 #[derive(PartialEq, Eq, Clone)]
-struct Assignments {
+pub struct Assignments {
     parts: RangeInclusive<i32>,
 }
 
 impl Assignments {
-    fn new(input: &str) -> Assignments {
-        let (start, end) = input.split_once('-').unwrap();
-        let option: RangeInclusive<i32> = start.parse().ok().unwrap()..=(end.parse().ok().unwrap());
-        Assignments { parts: option }
+    pub fn new(input: &str) -> Option<Assignments> {
+        let (start, end) = input.split_once('-')?;
+        let option: RangeInclusive<i32> = start.parse().ok()?..=(end.parse().ok()?);
+        Some(Assignments { parts: option })
     }
 
-    fn envelops(&self, other: &Assignments) -> bool {
+    pub fn envelops(&self, other: &Assignments) -> bool {
         other.parts.start() >= self.parts.start() && other.parts.end() <= self.parts.end()
     }
 
-    fn count_overlaps(&self, other: &Assignments) -> usize {
+    pub fn count_overlaps(&self, other: &Assignments) -> usize {
         other
             .parts
             .clone()
-            .into_iter()
             .filter(|x| self.parts.contains(x))
             .count()
     }
 }
 
-fn part1(payload: &str) {
-    let count = payload
+fn read_pairs(payload: &str) -> Result<Vec<(Assignments, Assignments)>, ParseError> {
+    payload
         .lines()
-        .filter_map(|l| {
-            let (a, b) = l.split_once(',')?;
-            Some((a, b))
+        .enumerate()
+        .map(|(i, l)| {
+            let (a, b) = l
+                .split_once(',')
+                .ok_or_else(|| ParseError::new(i + 1, 1, l, "expected two assignments"))?;
+            let assignment = |input: &str| {
+                Assignments::new(input)
+                    .ok_or_else(|| ParseError::within(i + 1, l, input, "expected a section range"))
+            };
+            Ok((assignment(a)?, assignment(b)?))
         })
-        .map(|(a, b)| (Assignments::new(a), Assignments::new(b)))
+        .collect()
+}
+
+/// How many pairs have one assignment fully containing the other.
+pub fn part1(pairs: &[(Assignments, Assignments)]) -> usize {
+    pairs
+        .iter()
         .filter(|(a, b)| a.envelops(b) || b.envelops(a))
-        .count();
-    println!("Part 1: {}", count);
+        .count()
 }
 
-fn part2(payload: &str) {
-    let count = payload
-        .lines()
-        .filter_map(|l| {
-            let (a, b) = l.split_once(',')?;
-            Some((a, b))
-        })
-        .map(|(a, b)| (Assignments::new(a), Assignments::new(b)))
+/// How many pairs have overlapping assignments.
+pub fn part2(pairs: &[(Assignments, Assignments)]) -> usize {
+    pairs
+        .iter()
         .filter(|(a, b)| a.count_overlaps(b) > 0)
-        .count();
-    println!("Part 2: {}", count);
+        .count()
 }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+impl Solution for Day04 {
+    type Model = Vec<(Assignments, Assignments)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_pairs(input)
+    }
+
+    fn part1(&self, pairs: &Self::Model) -> Answer {
+        part1(pairs).into()
+    }
+
+    fn part2(&self, pairs: &Self::Model) -> Answer {
+        part2(pairs).into()
+    }
 }
 
 #[cfg(test)]
@@ -71,11 +101,9 @@ mod tests {
     #[case("6-6", "4-6", true)] // 6-6,4-6
     #[case("2-6", "4-8", false)] // 2-6,4-8
     fn envelops(#[case] this: String, #[case] that: String, #[case] expected: bool) {
-        assert_eq!(
-            Assignments::new(this.as_str()).envelops(&Assignments::new(that.as_str()))
-                || Assignments::new(that.as_str()).envelops(&Assignments::new(this.as_str())),
-            expected
-        )
+        let this = Assignments::new(this.as_str()).unwrap();
+        let that = Assignments::new(that.as_str()).unwrap();
+        assert_eq!(this.envelops(&that) || that.envelops(&this), expected)
     }
 
     #[rstest]
@@ -86,9 +114,14 @@ mod tests {
     #[case("6-6", "4-6", 1)] // 6-6,4-6
     #[case("2-6", "4-8", 3)] // 2-6,4-8
     fn overlaps(#[case] this: String, #[case] that: String, #[case] expected: usize) {
-        assert_eq!(
-            Assignments::new(this.as_str()).count_overlaps(&Assignments::new(that.as_str())),
-            expected
-        )
+        let this = Assignments::new(this.as_str()).unwrap();
+        let that = Assignments::new(that.as_str()).unwrap();
+        assert_eq!(this.count_overlaps(&that), expected)
+    }
+
+    #[test]
+    fn invalid_range() {
+        let error = read_pairs("2-4,6-8\n2-3,4-x\n").err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 5, "4-x"));
     }
 }
@@ -1,7 +1,21 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use itertools::Itertools;
 
+pub const DAY: Day = Day {
+    number: 5,
+    title: "Supply Stacks",
+    solution: &Day05,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/05.txt"), "CMZ"),
+        Sample::new(Part::Two, include_str!("samples/05.txt"), "MCD"),
+    ],
+};
+
+pub struct Day05;
+
+/// The stacks of crates as moved by a crane of the given model.
 #[derive(PartialEq, Eq, Clone)]
-struct Camp {
+pub struct Camp {
     positions: Vec<Vec<Crate>>,
     version: u16,
 }
@@ -11,22 +25,31 @@ struct Crate {
     id: char,
 }
 
+/// The starting stacks and the rearrangement procedure, as given by the input.
+pub struct Procedure {
+    stacks: Vec<Vec<Crate>>,
+    moves: Vec<(usize, usize, usize)>,
+}
+
 impl Camp {
     fn new(positions: Vec<Vec<Crate>>, version: u16) -> Camp {
         Camp { positions, version }
     }
 
-    fn move_crates(&mut self, crate_count: usize, from: usize, to: usize) {
+    /// Moves the top `crate_count` crates, or returns `None` if the stack is too low.
+    fn move_crates(&mut self, crate_count: usize, from: usize, to: usize) -> Option<()> {
         let from = &mut self.positions[from - 1];
-        let crates = from.split_off(from.len() - crate_count);
+        let crates = from.split_off(from.len().checked_sub(crate_count)?);
         if self.version == 9000 {
             self.positions[to - 1].extend(crates.iter().cloned().rev());
         } else {
             self.positions[to - 1].extend(crates.iter().cloned());
         }
+        Some(())
     }
 
-    fn get_top_row(&self) -> String {
+    /// The crates on top of each stack.
+    pub fn get_top_row(&self) -> String {
         self.positions
             .iter()
             .filter_map(|m| m.last().map(|c| c.id))
@@ -34,69 +57,106 @@ impl Camp {
     }
 }
 
-fn simulate(payload: &str, version: u16) -> Camp {
-    let (init, moves) = payload.split_once("\n\n").unwrap();
-    let mut stack_iter = init.lines().rev();
-    let mut stack = vec![vec![]; stack_iter.next().unwrap().len() / 4 + 1];
+fn read_procedure(payload: &str) -> Result<Procedure, ParseError> {
+    let (init, moves) = payload
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::end_of(payload, "expected a blank line before the moves"))?;
+    let init: Vec<&str> = init.lines().collect();
+    let init_lines = init.len();
+    let mut stack_iter = init.iter().enumerate().rev();
+    let (_, labels) = stack_iter
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "expected the starting stacks"))?;
+    let mut stack = vec![vec![]; labels.len() / 4 + 1];
 
-    stack_iter.for_each(|l| {
-        l.chars().skip(1).enumerate().for_each(|(i, c)| {
+    for (row, l) in stack_iter {
+        for (i, (offset, c)) in l.char_indices().skip(1).enumerate() {
             if i % 4 == 0 && c != ' ' {
-                stack[i / 4].push(Crate { id: c });
+                let outside = || ParseError::at(row + 1, l, offset, "no such stack");
+                stack
+                    .get_mut(i / 4)
+                    .ok_or_else(outside)?
+                    .push(Crate { id: c });
             }
-        });
-    });
-
-    let mut camp = Camp::new(stack, version);
+        }
+    }
 
-    moves
+    let moves = moves
         .lines()
-        .map(|l| {
-            let s: Vec<&str> = l.split_ascii_whitespace().collect();
-            (
-                s[1].parse().unwrap(),
-                s[3].parse().unwrap(),
-                s[5].parse().unwrap(),
-            )
+        .enumerate()
+        .map(|(i, l)| {
+            let line = init_lines + 2 + i;
+            let invalid = || ParseError::new(line, 1, l, "expected move <n> from <a> to <b>");
+            match l.split_ascii_whitespace().collect::<Vec<&str>>()[..] {
+                ["move", count, "from", from, "to", to] => {
+                    let count = count.parse().map_err(|_| invalid())?;
+                    let stack_index = |s: &str| match s.parse() {
+                        Ok(index) if (1..=stack.len()).contains(&index) => Ok(index),
+                        _ => Err(ParseError::within(line, l, s, "unknown stack")),
+                    };
+                    Ok((count, stack_index(from)?, stack_index(to)?))
+                }
+                _ => Err(invalid()),
+            }
         })
-        .for_each(|(x, y, z)| camp.move_crates(x, y, z));
-    camp
+        .collect::<Result<_, _>>()?;
+    Ok(Procedure {
+        stacks: stack,
+        moves,
+    })
 }
 
-fn part1(payload: &str) {
-    println!("{}", simulate(payload, 9000).get_top_row());
+/// Rearranges the crates with a CrateMover of the given `version`, 9000 or 9001, or
+/// returns `None` if a move takes more crates than its stack holds.
+pub fn simulate(procedure: &Procedure, version: u16) -> Option<Camp> {
+    let mut camp = Camp::new(procedure.stacks.clone(), version);
+    procedure
+        .moves
+        .iter()
+        .try_for_each(|&(x, y, z)| camp.move_crates(x, y, z))?;
+    Some(camp)
 }
 
-fn part2(payload: &str) {
-    println!("{}", simulate(payload, 9001).get_top_row());
+fn top_row(procedure: &Procedure, version: u16) -> Answer {
+    let top_row = simulate(procedure, version).map(|camp| camp.get_top_row());
+    Answer::or_no_solution(top_row, "a move takes more crates than its stack holds")
 }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+impl Solution for Day05 {
+    type Model = Procedure;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_procedure(input)
+    }
+
+    fn part1(&self, procedure: &Self::Model) -> Answer {
+        top_row(procedure, 9000)
+    }
+
+    fn part2(&self, procedure: &Self::Model) -> Answer {
+        top_row(procedure, 9001)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static EXAMPLE: &str = concat!(
-        "    [D]    \n",
-        "[N] [C]\n",
-        "[Z] [M] [P]\n",
-        " 1   2   3 \n",
-        "\n",
-        "move 1 from 2 to 1\n",
-        "move 3 from 1 to 3\n",
-        "move 2 from 2 to 1\n",
-        "move 1 from 1 to 2\n"
-    );
+    const EXAMPLE: &str = include_str!("samples/05.txt");
 
     #[test]
     fn simulate_9000() {
-        assert_eq!(simulate(EXAMPLE, 9000).get_top_row(), "CMZ")
+        let camp = simulate(&read_procedure(EXAMPLE).unwrap(), 9000).unwrap();
+        assert_eq!(camp.get_top_row(), "CMZ")
     }
     #[test]
     fn simulate_9001() {
-        assert_eq!(simulate(EXAMPLE, 9001).get_top_row(), "MCD")
+        let camp = simulate(&read_procedure(EXAMPLE).unwrap(), 9001).unwrap();
+        assert_eq!(camp.get_top_row(), "MCD")
+    }
+    #[test]
+    fn too_few_crates() {
+        let procedure = read_procedure("[A]\n 1   2 \n\nmove 5 from 1 to 2\n").unwrap();
+        assert!(simulate(&procedure, 9000).is_none());
+        assert!(matches!(Day05.part2(&procedure), Answer::NoSolution(_)));
     }
 }
@@ -1,6 +1,19 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use itertools::Itertools;
 
-struct SignalDevice {
+pub const DAY: Day = Day {
+    number: 6,
+    title: "Tuning Trouble",
+    solution: &Day06,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/06.txt"), "7"),
+        Sample::new(Part::Two, include_str!("samples/06.txt"), "19"),
+    ],
+};
+
+pub struct Day06;
+
+pub struct SignalDevice {
     data_stream: String,
 }
 
@@ -9,28 +22,30 @@ impl SignalDevice {
         Self { data_stream }
     }
 
-    pub fn lock_signal(&self, marker: usize) -> usize {
+    pub fn lock_signal(&self, marker: usize) -> Option<usize> {
         self.data_stream
             .chars()
             .collect::<Vec<char>>()
             .windows(marker)
             .find_position(|x| x.iter().all_unique())
             .map(|(x, _)| x + marker)
-            .unwrap()
     }
 }
 
-fn part1(payload: String) {
-    println!("{}", SignalDevice::new(payload).lock_signal(4));
-}
+impl Solution for Day06 {
+    type Model = SignalDevice;
 
-fn part2(payload: String) {
-    println!("{}", SignalDevice::new(payload).lock_signal(14));
-}
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        Ok(SignalDevice::new(input.to_string()))
+    }
 
-pub fn run(payload: &str) {
-    part1(payload.to_string());
-    part2(payload.to_string());
+    fn part1(&self, device: &Self::Model) -> Answer {
+        Answer::or_no_solution(device.lock_signal(4), "no start-of-packet marker")
+    }
+
+    fn part2(&self, device: &Self::Model) -> Answer {
+        Answer::or_no_solution(device.lock_signal(14), "no start-of-message marker")
+    }
 }
 
 #[cfg(test)]
@@ -50,6 +65,11 @@ mod tests {
     #[case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14, 29)]
     #[case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14, 26)]
     fn lock_signal(#[case] this: String, #[case] marker: usize, #[case] expected: usize) {
-        assert_eq!(SignalDevice::new(this).lock_signal(marker), expected)
+        assert_eq!(SignalDevice::new(this).lock_signal(marker), Some(expected))
+    }
+
+    #[test]
+    fn no_marker() {
+        assert_eq!(SignalDevice::new("abcabcabc".into()).lock_signal(4), None)
     }
 }
@@ -1,15 +1,27 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use itertools::Itertools;
 use std::borrow::{Borrow, Cow};
 
+pub const DAY: Day = Day {
+    number: 7,
+    title: "No Space Left On Device",
+    solution: &Day07,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/07.txt"), "95437"),
+        Sample::new(Part::Two, include_str!("samples/07.txt"), "24933642"),
+    ],
+};
+
+pub struct Day07;
+
 #[derive(Debug)]
-struct Filesystem {
+pub struct Filesystem {
     total_fs_size: Cow<'static, usize>,
     nodes: Vec<Node>,
 }
 
 #[derive(Debug)]
 struct Node {
-    inode: usize,
     fs_node: FsNode,
     parent: Option<usize>,
     children: Vec<usize>,
@@ -22,61 +34,50 @@ enum FsNode {
 }
 
 impl Filesystem {
-    pub fn new(cli_log: &str) -> Self {
+    pub fn new(cli_log: &str) -> Result<Self, ParseError> {
         let mut filesystem = Self {
             total_fs_size: Cow::Owned(70000000),
             nodes: vec![],
         };
         let root_node_idx = filesystem.insert_node(None, FsNode::Directory { name: "".into() });
         let mut node_pwd = root_node_idx;
-        cli_log.lines().for_each(|l| {
+        for (i, l) in cli_log.lines().enumerate() {
             let parts: Vec<&str> = l.split_whitespace().collect();
-            if parts[0].eq("$") {
-                match parts[1] {
-                    "cd" => {
-                        if parts[2] == "/" {
-                            node_pwd = root_node_idx;
-                        } else if parts[2] == ".." {
-                            node_pwd = filesystem
-                                .nodes
-                                .iter()
-                                .find(|n| n.inode.eq(&node_pwd))
-                                .unwrap()
-                                .parent
-                                .unwrap();
-                        } else {
-                            for c in &filesystem.nodes.get(node_pwd).unwrap().children {
-                                let idx = *c;
-                                if let FsNode::Directory { name } =
-                                    &filesystem.nodes.get(idx).unwrap().fs_node
-                                {
-                                    if name == parts[2] {
-                                        node_pwd = *c;
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    "ls" => {}
-                    _ => {
-                        panic!("Invalid command {}", parts[1]);
-                    }
+            match parts[..] {
+                ["$", "cd", "/"] => node_pwd = root_node_idx,
+                ["$", "cd", parent @ ".."] => {
+                    node_pwd = filesystem.nodes[node_pwd]
+                        .parent
+                        .ok_or_else(|| ParseError::within(i + 1, l, parent, "root has no parent"))?;
                 }
-            } else {
-                let node = if parts[0].eq("dir") {
-                    FsNode::Directory {
-                        name: parts[1].into(),
-                    }
-                } else {
-                    FsNode::File {
-                        size: parts[0].parse().unwrap(),
-                    }
-                };
-                filesystem.insert_node(Some(node_pwd), node);
+                ["$", "cd", dir] => {
+                    node_pwd = *filesystem.nodes[node_pwd]
+                        .children
+                        .iter()
+                        .find(|&&c| match &filesystem.nodes[c].fs_node {
+                            FsNode::Directory { name } => name == dir,
+                            FsNode::File { .. } => false,
+                        })
+                        .ok_or_else(|| ParseError::within(i + 1, l, dir, "unknown directory"))?;
+                }
+                ["$", "ls"] => {}
+                ["$", command, ..] => {
+                    return Err(ParseError::within(i + 1, l, command, "invalid command"));
+                }
+                ["dir", name] => {
+                    let node = FsNode::Directory { name: name.into() };
+                    filesystem.insert_node(Some(node_pwd), node);
+                }
+                [size, _] => {
+                    let size = size
+                        .parse()
+                        .map_err(|_| ParseError::within(i + 1, l, size, "invalid file size"))?;
+                    filesystem.insert_node(Some(node_pwd), FsNode::File { size });
+                }
+                _ => return Err(ParseError::new(i + 1, 1, l, "expected a command or listing")),
             }
-        });
-        filesystem
+        }
+        Ok(filesystem)
     }
 
     pub fn get_size_over(&self, limit: usize) -> usize {
@@ -115,7 +116,6 @@ impl Filesystem {
     fn insert_node(&mut self, parent: Option<usize>, fs_node: FsNode) -> usize {
         let inode = self.nodes.len();
         self.nodes.push(Node {
-            inode,
             fs_node,
             parent,
             children: vec![],
@@ -127,61 +127,37 @@ impl Filesystem {
     }
 }
 
-fn part1(payload: &str) {
-    println!("{}", Filesystem::new(payload).get_size_over(100000));
-}
+impl Solution for Day07 {
+    type Model = Filesystem;
 
-fn part2(payload: &str) {
-    println!(
-        "{}",
-        Filesystem::new(payload).get_smallest_candidate(30000000)
-    );
-}
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        Filesystem::new(input)
+    }
+
+    fn part1(&self, filesystem: &Self::Model) -> Answer {
+        filesystem.get_size_over(100000).into()
+    }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+    fn part2(&self, filesystem: &Self::Model) -> Answer {
+        filesystem.get_smallest_candidate(30000000).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static EXAMPLE: &str = concat!(
-        "$ cd /\n",
-        "$ ls\n",
-        "dir a\n",
-        "14848514 b.txt\n",
-        "8504156 c.dat\n",
-        "dir d\n",
-        "$ cd a\n",
-        "$ ls\n",
-        "dir e\n",
-        "29116 f\n",
-        "2557 g\n",
-        "62596 h.lst\n",
-        "$ cd e\n",
-        "$ ls\n",
-        "584 i\n",
-        "$ cd ..\n",
-        "$ cd ..\n",
-        "$ cd d\n",
-        "$ ls\n",
-        "4060174 j\n",
-        "8033020 d.log\n",
-        "5626152 d.ext\n",
-        "7214296 k\n"
-    );
+    const EXAMPLE: &str = include_str!("samples/07.txt");
 
     #[test]
     fn get_size_over() {
-        assert_eq!(Filesystem::new(EXAMPLE).get_size_over(100000), 95437)
+        assert_eq!(Filesystem::new(EXAMPLE).unwrap().get_size_over(100000), 95437)
     }
 
     #[test]
     fn get_smallest_candidate() {
         assert_eq!(
-            Filesystem::new(EXAMPLE).get_smallest_candidate(30000000),
+            Filesystem::new(EXAMPLE).unwrap().get_smallest_candidate(30000000),
             24933642
         )
     }
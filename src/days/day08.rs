@@ -1,139 +1,99 @@
-use grid::Grid;
-use std::cmp::Ordering;
-
-enum View {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-struct TreePatch {
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use crate::geometry::{Direction, Grid, UPoint};
+
+pub const DAY: Day = Day {
+    number: 8,
+    title: "Treetop Tree House",
+    solution: &Day08,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/08.txt"), "21"),
+        Sample::new(Part::Two, include_str!("samples/08.txt"), "8"),
+    ],
+};
+
+pub struct Day08;
+
+pub struct TreePatch {
     trees: Grid<u32>,
 }
 
 impl TreePatch {
-    pub fn new(payload: &str) -> Self {
-        let mut trees: Grid<u32> = Grid::new(0, 0);
-        payload
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|digit| digit.to_digit(10).unwrap())
-                    .collect()
-            })
-            .for_each(|row| trees.push_row(row));
-        Self { trees }
+    pub fn new(payload: &str) -> Result<Self, ParseError> {
+        let trees = Grid::parse(payload, |c| c.to_digit(10), "expected a height")?;
+        Ok(Self { trees })
     }
 
-    pub fn count_visible_trees(&self) -> usize {
-        let mut visible_counter: usize = (self.trees.rows() + self.trees.cols()) * 2 - 4;
-
-        for row in 1..self.trees.rows() - 1 {
-            for column in 1..self.trees.cols() - 1 {
-                if self
-                    .get_directions(row, column)
-                    .iter()
-                    .map(|sequence| sequence.1.iter().any(|&x| x >= self.trees[row][column]))
-                    .any(|invisible| !invisible)
-                {
-                    visible_counter += 1
-                }
-            }
+    pub fn count_visible_trees(&self) -> Option<usize> {
+        if self.trees.width() == 0 || self.trees.height() == 0 {
+            return None;
         }
-        visible_counter
+        let visible = self.trees.points().filter(|&tree| {
+            Direction::ALL.into_iter().any(|direction| {
+                self.trees
+                    .ray(tree, direction)
+                    .all(|other| self.trees[other] < self.trees[tree])
+            })
+        });
+        Some(visible.count())
     }
 
     pub fn scenic_scores(&self) -> Vec<usize> {
-        let mut scenic_scores: Vec<usize> = vec![];
-
-        for row in 0..self.trees.rows() {
-            for column in 0..self.trees.cols() {
-                let target = &self.trees[row][column];
-
-                let test: Vec<usize> = self
-                    .get_directions(row, column)
-                    .iter()
-                    .map(|val| {
-                        let mut counter = 0;
-                        let (direction, sequence) = val;
-                        let mut numbers = sequence.clone();
-
-                        match direction {
-                            View::Left | View::Up => {
-                                numbers = sequence.iter().rev().copied().collect();
-                            }
-                            _ => (),
-                        }
-
-                        for nr in &numbers {
-                            match nr.cmp(target) {
-                                Ordering::Less => counter += 1,
-                                Ordering::Equal | Ordering::Greater => {
-                                    counter += 1;
-                                    break;
-                                }
-                            }
-                        }
-                        counter
-                    })
-                    .collect();
-
-                scenic_scores.push(test.iter().product());
+        self.trees
+            .points()
+            .map(|tree| {
+                Direction::ALL
+                    .into_iter()
+                    .map(|direction| self.viewing_distance(tree, direction))
+                    .product()
+            })
+            .collect()
+    }
+
+    /// How many trees can be seen from `tree` looking in `direction`, up to and including
+    /// the first one at least as tall.
+    fn viewing_distance(&self, tree: UPoint, direction: Direction) -> usize {
+        let mut seen = 0;
+        for other in self.trees.ray(tree, direction) {
+            seen += 1;
+            if self.trees[other] >= self.trees[tree] {
+                break;
             }
         }
-        scenic_scores
-    }
-    fn get_directions(&self, row: usize, pos: usize) -> Vec<(View, Vec<u32>)> {
-        vec![
-            (View::Left, self.trees[row][..pos].to_vec()),
-            (View::Right, self.trees[row][pos + 1..].to_vec()),
-            (
-                View::Up,
-                self.trees.iter_col(pos).take(row).cloned().collect(),
-            ),
-            (
-                View::Down,
-                self.trees.iter_col(pos).skip(row + 1).cloned().collect(),
-            ),
-        ]
+        seen
     }
 }
 
-fn part1(payload: &str) {
-    println!("{}", TreePatch::new(payload).count_visible_trees())
-}
+impl Solution for Day08 {
+    type Model = TreePatch;
 
-fn part2(payload: &str) {
-    println!(
-        "{}",
-        *TreePatch::new(payload)
-            .scenic_scores()
-            .iter()
-            .max()
-            .unwrap()
-    )
-}
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        TreePatch::new(input)
+    }
+
+    fn part1(&self, trees: &Self::Model) -> Answer {
+        Answer::or_no_solution(trees.count_visible_trees(), "no trees")
+    }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+    fn part2(&self, trees: &Self::Model) -> Answer {
+        Answer::or_no_solution(trees.scenic_scores().into_iter().max(), "no trees")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static EXAMPLE: &str = concat!("30373\n", "25512\n", "65332\n", "33549\n", "35390\n");
+    const EXAMPLE: &str = include_str!("samples/08.txt");
 
     #[test]
     fn count_visible_trees() {
-        assert_eq!(TreePatch::new(EXAMPLE).count_visible_trees(), 21)
+        assert_eq!(TreePatch::new(EXAMPLE).unwrap().count_visible_trees(), Some(21))
     }
 
     #[test]
     fn max_scenic_scores() {
         assert_eq!(
-            *TreePatch::new(EXAMPLE)
+            *TreePatch::new(EXAMPLE).unwrap()
                 .scenic_scores()
                 .iter()
                 .max()
@@ -141,4 +101,14 @@ mod tests {
             8
         )
     }
+
+    #[test]
+    fn single_row() {
+        assert_eq!(TreePatch::new("30373").unwrap().count_visible_trees(), Some(5))
+    }
+
+    #[test]
+    fn no_trees() {
+        assert_eq!(TreePatch::new("").unwrap().count_visible_trees(), None)
+    }
 }
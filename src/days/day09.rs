@@ -1,49 +1,49 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use crate::geometry::{Direction, Point};
 use std::collections::HashSet;
 
-type Coord = (i32, i32);
+pub const DAY: Day = Day {
+    number: 9,
+    title: "Rope Bridge",
+    solution: &Day09,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/09.txt"), "13"),
+        Sample::new(Part::Two, include_str!("samples/09.txt"), "1"),
+        Sample::new(Part::Two, include_str!("samples/09-2.txt"), "36"),
+    ],
+};
 
-struct Map {
-    knots: [Coord; 10],
-    visited_1: HashSet<Coord>,
-    visited_9: HashSet<Coord>,
+pub struct Day09;
+
+/// A move of the head: a direction and how many steps to take in it.
+pub type Motion = (Direction, u32);
+
+/// A rope of ten knots and the positions its second and last knots have visited.
+pub struct Map {
+    knots: [Point; 10],
+    pub visited_1: HashSet<Point>,
+    pub visited_9: HashSet<Point>,
 }
 
 impl Map {
     pub fn new() -> Self {
-        let knots: [Coord; 10] = [(0, 0); 10];
-        let mut visited_1: HashSet<Coord> = HashSet::new();
-        let mut visited_9: HashSet<Coord> = HashSet::new();
-        visited_1.insert(knots[1]);
-        visited_9.insert(knots[9]);
+        let knots = [Point::ORIGIN; 10];
         Map {
             knots,
-            visited_1,
-            visited_9,
+            visited_1: HashSet::from([knots[1]]),
+            visited_9: HashSet::from([knots[9]]),
         }
     }
 
-    pub fn simulate_steps(&mut self, payload: &str) -> &mut Self {
-        for line in payload.lines() {
-            let (dir, num_steps) = line.split_once(' ').unwrap();
-            let num_steps: u32 = num_steps.parse().unwrap();
-            let delta = match dir {
-                "L" => (-1, 0),
-                "R" => (1, 0),
-                "U" => (0, -1),
-                "D" => (0, 1),
-                _ => unreachable!(),
-            };
-
+    pub fn simulate_steps(&mut self, motions: &[Motion]) -> &mut Self {
+        for &(direction, num_steps) in motions {
             for _ in 0..num_steps {
-                self.knots[0] = (self.knots[0].0 + delta.0, self.knots[0].1 + delta.1);
+                self.knots[0] += direction.delta();
 
                 for i in 1..10 {
-                    let dx = self.knots[i - 1].0 - self.knots[i].0;
-                    let dy = self.knots[i - 1].1 - self.knots[i].1;
-
-                    if dx.abs() > 1 || dy.abs() > 1 {
-                        self.knots[i].0 += dx.signum();
-                        self.knots[i].1 += dy.signum();
+                    let (head, tail) = (self.knots[i - 1], self.knots[i]);
+                    if tail.chebyshev(head) > 1 {
+                        self.knots[i] += (head - tail).signum();
                     }
                 }
 
@@ -55,41 +55,72 @@ impl Map {
     }
 }
 
-fn part1(payload: &str) {
-    println!(
-        "Day 9 - Part 1: {}",
-        Map::new().simulate_steps(payload).visited_1.len()
-    )
+impl Default for Map {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-fn part2(payload: &str) {
-    println!(
-        "Day 9 - Part 2: {}",
-        Map::new().simulate_steps(payload).visited_9.len()
-    )
+fn read_motions(payload: &str) -> Result<Vec<Motion>, ParseError> {
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (dir, num_steps) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::new(i + 1, 1, line, "expected a direction and steps"))?;
+            let num_steps: u32 = num_steps
+                .parse()
+                .map_err(|_| ParseError::within(i + 1, line, num_steps, "invalid step count"))?;
+            let direction = match dir {
+                "L" => Direction::Left,
+                "R" => Direction::Right,
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                _ => return Err(ParseError::new(i + 1, 1, dir, "expected L, R, U or D")),
+            };
+            Ok((direction, num_steps))
+        })
+        .collect()
 }
 
-pub fn run(payload: &str) {
-    part1(payload);
-    part2(payload);
+impl Solution for Day09 {
+    type Model = Vec<Motion>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_motions(input)
+    }
+
+    fn part1(&self, motions: &Self::Model) -> Answer {
+        Map::new().simulate_steps(motions).visited_1.len().into()
+    }
+
+    fn part2(&self, motions: &Self::Model) -> Answer {
+        Map::new().simulate_steps(motions).visited_9.len().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE1: &str =
-        concat!("R 4\n", "U 4\n", "L 3\n", "D 1\n", "R 4\n", "D 1\n", "L 5\n", "R 2\n");
-    const EXAMPLE2: &str =
-        concat!("R 5\n", "U 8\n", "L 8\n", "D 3\n", "R 17\n", "D 10\n", "L 25\n", "U 20");
+    const EXAMPLE1: &str = include_str!("samples/09.txt");
+    const EXAMPLE2: &str = include_str!("samples/09-2.txt");
 
     #[test]
     fn knots_2() {
-        assert_eq!(Map::new().simulate_steps(EXAMPLE1).visited_1.len(), 13)
+        assert_eq!(Map::new().simulate_steps(&read_motions(EXAMPLE1).unwrap()).visited_1.len(), 13)
     }
 
     #[test]
     fn knots_10() {
-        assert_eq!(Map::new().simulate_steps(EXAMPLE2).visited_9.len(), 36)
+        assert_eq!(Map::new().simulate_steps(&read_motions(EXAMPLE2).unwrap()).visited_9.len(), 36)
+    }
+
+    #[test]
+    fn invalid_direction() {
+        let error = read_motions("R 4\nX 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.to_string(), "line 2, column 1: expected L, R, U or D at \"X\"");
     }
 }
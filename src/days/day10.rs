@@ -1,13 +1,45 @@
-use itertools::Itertools;
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use crate::geometry::{Grid, UPoint};
 
 const WIDTH: usize = 40;
 const HEIGHT: usize = 6;
 
-struct Cpu {
+pub const DAY: Day = Day {
+    number: 10,
+    title: "Cathode-Ray Tube",
+    solution: &Day10,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/10.txt"), "13140"),
+        Sample::new(
+            Part::Two,
+            include_str!("samples/10.txt"),
+            concat!(
+                "##..##..##..##..##..##..##..##..##..##..\n",
+                "###...###...###...###...###...###...###.\n",
+                "####....####....####....####....####....\n",
+                "#####.....#####.....#####.....#####.....\n",
+                "######......######......######......####\n",
+                "#######.......#######.......#######....."
+            ),
+        ),
+    ],
+};
+
+pub struct Day10;
+
+/// An instruction of the handheld's CPU.
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+/// The CPU and the CRT it drives.
+pub struct Cpu {
     x: i32,
     cycle: i32,
-    signal_strength: i32,
-    screen: [[bool; WIDTH]; HEIGHT],
+    /// The summed signal strengths during the 20th, 60th, 100th, ... cycles.
+    pub signal_strength: i32,
+    screen: Grid<bool>,
 }
 
 impl Cpu {
@@ -16,21 +48,20 @@ impl Cpu {
             x: 1,
             cycle: 0,
             signal_strength: 0,
-            screen: [[false; WIDTH]; HEIGHT],
+            screen: Grid::new(WIDTH, HEIGHT, false),
         }
     }
 
     pub fn print_screen(&self) -> String {
-        self.screen
-            .iter()
-            .map(|row| row.iter().map(|&x| if x { "#" } else { "." }).join(""))
-            .join("\n")
+        self.screen.render(|&lit| if lit { '#' } else { '.' })
     }
 
     fn tick(&mut self) {
         let y = (self.cycle / WIDTH as i32) as usize;
         let x = (self.cycle % WIDTH as i32) as usize;
-        self.screen[y][x] = self.x.abs_diff(x as i32) <= 1;
+        if let Some(pixel) = self.screen.get_mut(UPoint::new(x, y)) {
+            *pixel = self.x.abs_diff(x as i32) <= 1;
+        }
         self.cycle += 1;
 
         if self.cycle % 40 == 20 {
@@ -49,16 +80,51 @@ impl Cpu {
     }
 }
 
-pub fn run(payload: &str) {
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_program(payload: &str) -> Result<Vec<Instruction>, ParseError> {
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match line.split_once(' ') {
+            None if line == "noop" => Ok(Instruction::Noop),
+            Some(("addx", v)) => v
+                .parse()
+                .map(Instruction::Addx)
+                .map_err(|_| ParseError::within(i + 1, line, v, "invalid value")),
+            _ => Err(ParseError::new(i + 1, 1, line, "invalid command")),
+        })
+        .collect()
+}
+
+/// Runs the program on a fresh CPU.
+pub fn execute(program: &[Instruction]) -> Cpu {
     let mut cpu = Cpu::new();
-    payload.lines().for_each(|line| match &line[0..4] {
-        "noop" => cpu.noop(),
-        "addx" => cpu.addx(line[5..].parse().unwrap()),
-        _ => panic!("Invalid command {}", line),
+    program.iter().for_each(|instruction| match instruction {
+        Instruction::Noop => cpu.noop(),
+        Instruction::Addx(v) => cpu.addx(*v),
     });
+    cpu
+}
 
-    println!("Day 10 - Part 1: {}", cpu.signal_strength);
-    println!("Day 10 - Part 2:\n {}", cpu.print_screen());
+impl Solution for Day10 {
+    type Model = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_program(input)
+    }
+
+    fn part1(&self, program: &Self::Model) -> Answer {
+        execute(program).signal_strength.into()
+    }
+
+    fn part2(&self, program: &Self::Model) -> Answer {
+        Answer::Screen(execute(program).print_screen())
+    }
 }
 
 #[cfg(test)]
@@ -69,15 +135,7 @@ mod tests {
     const SAMPLE: &str = include_str!("samples/10.txt");
 
     lazy_static! {
-        static ref EXAMPLE_CPU: Cpu = {
-            let mut _cpu = Cpu::new();
-            SAMPLE.lines().for_each(|line| match &line[0..4] {
-                "noop" => _cpu.noop(),
-                "addx" => _cpu.addx(line[5..].parse().unwrap()),
-                _ => panic!("Invalid command"),
-            });
-            _cpu
-        };
+        static ref EXAMPLE_CPU: Cpu = execute(&read_program(SAMPLE).unwrap());
     }
 
     #[test]
@@ -1,9 +1,22 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use regex::Regex;
 use std::collections::BinaryHeap;
 use std::str::FromStr;
 
+pub const DAY: Day = Day {
+    number: 11,
+    title: "Monkey in the Middle",
+    solution: &Day11,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/11.txt"), "10605"),
+        Sample::new(Part::Two, include_str!("samples/11.txt"), "2713310158"),
+    ],
+};
+
+pub struct Day11;
+
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct Monkey {
+pub struct Monkey {
     id: usize,
     items: Vec<u64>,
     operation: Operation,
@@ -21,49 +34,45 @@ enum Operation {
 }
 
 impl FromStr for Monkey {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let re = Regex::new(r"^Monkey (?P<id>\d+):\n\s+Starting items: (?P<items>[\d,\s]*)\n\s+Operation: new = old (?P<operation>\S+)\s(?P<operation_arg>\S+)\n\s+Test: divisible by (?P<divisible_by>\d+)\n\s+\S+ true: throw to monkey (?P<if_true>\d+)\n\s+\S+ false: throw to monkey (?P<if_false>\d+)$").unwrap();
+        let header = s.lines().next().unwrap_or("");
         let captures = re
             .captures(s)
-            .ok_or(format!("Invalid input format for {}", s))?;
+            .ok_or_else(|| ParseError::new(1, 1, header, "invalid monkey"))?;
+
+        let at = |name: &str, message: &str| {
+            let m = captures.name(name).unwrap();
+            let line_number = s[..m.start()].matches('\n').count() + 1;
+            let line = s.lines().nth(line_number - 1).unwrap();
+            ParseError::within(line_number, line, &s[m.range()], message)
+        };
+        let number = |name: &str| {
+            captures[name]
+                .parse()
+                .map_err(|_| at(name, "number out of range"))
+        };
 
-        let id = captures.name("id").unwrap().as_str().parse().unwrap();
-        let items = captures
-            .name("items")
-            .unwrap()
-            .as_str()
+        let id = number("id")? as usize;
+        let items = captures["items"]
             .split(", ")
             .map(|s| s.parse())
             .collect::<Result<Vec<u64>, _>>()
-            .map_err(|e| e.to_string())?;
-        let operation = match captures.name("operation").unwrap().as_str() {
-            "+" => Operation::Add(
-                captures
-                    .name("operation_arg")
-                    .unwrap()
-                    .as_str()
-                    .parse()
-                    .unwrap(),
-            ),
-            "*" => {
-                if captures.name("operation_arg").unwrap().as_str().eq("old") {
-                    Operation::Square
-                } else {
-                    Operation::Multiply(captures[4].parse().unwrap())
-                }
-            }
-            _ => Operation::Square,
+            .map_err(|_| at("items", "expected a list of worry levels"))?;
+        let operation = match (&captures["operation"], &captures["operation_arg"]) {
+            ("*", "old") => Operation::Square,
+            ("+", _) => Operation::Add(number("operation_arg")?),
+            ("*", _) => Operation::Multiply(number("operation_arg")?),
+            _ => return Err(at("operation", "expected + or *")),
         };
-        let divisible_by = captures
-            .name("divisible_by")
-            .unwrap()
-            .as_str()
-            .parse()
-            .unwrap();
-        let if_true = captures.name("if_true").unwrap().as_str().parse().unwrap();
-        let if_false = captures.name("if_false").unwrap().as_str().parse().unwrap();
+        let divisible_by = number("divisible_by")?;
+        if divisible_by == 0 {
+            return Err(at("divisible_by", "cannot divide by zero"));
+        }
+        let if_true = number("if_true")? as usize;
+        let if_false = number("if_false")? as usize;
 
         Ok(Monkey {
             id,
@@ -77,8 +86,10 @@ impl FromStr for Monkey {
     }
 }
 
-fn calc_monkey_business(
-    monkeys: &mut Vec<Monkey>,
+/// Plays `rounds` rounds of keep away and multiplies the inspection counts of the two most
+/// active monkeys. `calm_func` keeps the worry levels in check after each inspection.
+pub fn calc_monkey_business(
+    monkeys: &mut [Monkey],
     rounds: usize,
     calm_func: impl Fn(u64) -> u64,
 ) -> u64 {
@@ -106,20 +117,43 @@ fn calc_monkey_business(
     inspections.iter().take(2).product()
 }
 
-pub fn run(payload: &str) {
-    let mut monkeys: Vec<Monkey> = payload
-        .split("\n\n")
-        .map(|monkey_def| Monkey::from_str(monkey_def.to_string().trim()).unwrap())
-        .collect();
-    let product: u64 = monkeys.iter().map(|m| m.divisible_by).product();
-    println!(
-        "Day 11 - Part 1: {}",
-        calc_monkey_business(&mut monkeys.clone(), 20, |x| x / 3)
-    );
-    println!(
-        "Day 11 - Part 2: {}",
-        calc_monkey_business(&mut monkeys, 10_000, |x| x % product)
-    );
+fn read_monkeys(payload: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut offset = 0;
+    let mut monkeys = vec![];
+    let mut headers = vec![];
+    for monkey_def in payload.split("\n\n") {
+        monkeys.push(Monkey::from_str(monkey_def.trim_end()).map_err(|e| e.shifted(offset))?);
+        headers.push((offset + 1, monkey_def.lines().next().unwrap_or("")));
+        offset += monkey_def.lines().count() + 1;
+    }
+    for (monkey, (line, header)) in monkeys.iter().zip(headers) {
+        if let Some(target) = [monkey.if_true, monkey.if_false]
+            .into_iter()
+            .find(|&target| target >= monkeys.len())
+        {
+            let message = format!("throws to unknown monkey {target}");
+            return Err(ParseError::new(line, 1, header, message));
+        }
+    }
+    Ok(monkeys)
+}
+
+impl Solution for Day11 {
+    type Model = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_monkeys(input)
+    }
+
+    fn part1(&self, monkeys: &Self::Model) -> Answer {
+        calc_monkey_business(&mut monkeys.clone(), 20, |x| x / 3).into()
+    }
+
+    fn part2(&self, monkeys: &Self::Model) -> Answer {
+        let mut monkeys = monkeys.clone();
+        let product: u64 = monkeys.iter().map(|m| m.divisible_by).product();
+        calc_monkey_business(&mut monkeys, 10_000, |x| x % product).into()
+    }
 }
 
 #[cfg(test)]
@@ -130,10 +164,7 @@ mod tests {
     const SAMPLE: &str = include_str!("samples/11.txt");
 
     lazy_static! {
-        static ref MONKEYS: Vec<Monkey> = SAMPLE
-            .split("\n\n")
-            .map(|monkey_def| Monkey::from_str(monkey_def.to_string().trim()).unwrap())
-            .collect();
+        static ref MONKEYS: Vec<Monkey> = read_monkeys(SAMPLE).unwrap();
     }
 
     #[test]
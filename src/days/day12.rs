@@ -1,165 +1,92 @@
-use pathfinding::prelude::bfs;
-use std::collections::HashMap;
-use std::fmt;
-use std::time::Instant;
-
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Coordinate {
-    pub row: usize,
-    pub col: usize,
-}
-
-impl Coordinate {
-    fn from(row: usize, col: usize) -> Coordinate {
-        Coordinate { row, col }
-    }
-}
-
-impl fmt::Display for Coordinate {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({}, {})", self.row, self.col)
-    }
-}
-
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use crate::geometry::{Grid, UPoint};
+use crate::search::{self, Search};
+
+pub const DAY: Day = Day {
+    number: 12,
+    title: "Hill Climbing Algorithm",
+    solution: &Day12,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/12.txt"), "31"),
+        Sample::new(Part::Two, include_str!("samples/12.txt"), "29"),
+    ],
+};
+
+pub struct Day12;
+
+/// The heightmap, with elevations from 0 for `a` to 25 for `z`.
 pub struct Graph {
-    pub grid: HashMap<Coordinate, char>,
-    pub start_loc: Option<Coordinate>,
-    pub end_loc: Option<Coordinate>,
-    pub extents: (usize, usize),
+    pub elevations: Grid<u8>,
+    pub start_loc: UPoint,
+    pub end_loc: UPoint,
 }
 
 impl Graph {
-    pub fn from(s: &str) -> Graph {
-        let mut grid: HashMap<Coordinate, char> = HashMap::<Coordinate, char>::new();
-        let mut start_loc: Option<Coordinate> = None;
-        let mut end_loc: Option<Coordinate> = None;
-        let mut row: usize = 0;
-        let mut col: usize = 0;
-        s.lines().for_each(|line| {
-            for c in line.chars() {
-                let coord = Coordinate { row, col };
-                match c {
-                    'S' => {
-                        start_loc = Some(coord);
-                        grid.insert(coord, 'a');
-                    }
-                    'E' => {
-                        end_loc = Some(coord);
-                        grid.insert(coord, 'z');
-                    }
-                    'a'..='z' => {
-                        grid.insert(coord, c);
-                    }
-                    _ => {
-                        panic!("main(): unexpected char in input!");
-                    }
-                }
-                col += 1;
-            }
-            row += 1;
-            col = 0;
-        });
-        let max_row: usize = grid.keys().map(|c| c.row).max().unwrap();
-        let max_col: usize = grid.keys().map(|c| c.col).max().unwrap();
-        let extents: (usize, usize) = (max_row, max_col);
-        Graph {
-            grid,
+    pub fn from(s: &str) -> Result<Graph, ParseError> {
+        let map = Grid::parse(
+            s,
+            |c| matches!(c, 'a'..='z' | 'S' | 'E').then_some(c),
+            "expected an elevation",
+        )?;
+        let start_loc = map
+            .position(|&c| c == 'S')
+            .ok_or_else(|| ParseError::end_of(s, "no start position S"))?;
+        let end_loc = map
+            .position(|&c| c == 'E')
+            .ok_or_else(|| ParseError::end_of(s, "no best signal position E"))?;
+        Ok(Graph {
+            elevations: map.map(|&c| Graph::elevation(c)),
             start_loc,
             end_loc,
-            extents,
-        }
+        })
     }
 
-    pub fn vertices(&self) -> Vec<Coordinate> {
-        let mut vertices: Vec<Coordinate> = self.grid.keys().cloned().collect();
-        vertices.sort();
-        vertices
+    pub fn elevation(c: char) -> u8 {
+        match c {
+            'S' => 0,
+            'E' => 25,
+            _ => c as u8 - b'a',
+        }
     }
 
-    pub fn elevation(c: char) -> i32 {
-        let elev: HashMap<char, i32> = ('a'..='z')
-            .collect::<Vec<char>>()
-            .into_iter()
-            .zip((1..=26).collect::<Vec<i32>>().into_iter())
-            .collect();
-        elev[&c]
+    /// The neighbours of `loc` that are at most one higher.
+    pub fn neighbors(&self, loc: UPoint) -> impl Iterator<Item = UPoint> + '_ {
+        let highest = self.elevations[loc] + 1;
+        self.elevations
+            .neighbours(loc)
+            .filter(move |&next| self.elevations[next] <= highest)
     }
+}
 
-    pub fn neighbors(&self, loc: Coordinate) -> Vec<Coordinate> {
-        let mut adjacents: Vec<Coordinate> = vec![];
-        let row = loc.row;
-        let col = loc.col;
-        if ((row as i32) - 1) >= 0 {
-            adjacents.push(Coordinate::from(row - 1, col));
-        }
-        if (row + 1) <= self.extents.0 {
-            adjacents.push(Coordinate::from(row + 1, col));
-        }
-        if ((col as i32) - 1) >= 0 {
-            adjacents.push(Coordinate::from(row, col - 1));
-        }
-        if (col + 1) <= self.extents.1 {
-            adjacents.push(Coordinate::from(row, col + 1));
-        }
-        let valid_neighbors: Vec<Coordinate> = adjacents
-            .into_iter()
-            .filter(|x| Graph::elevation(self.grid[x]) <= Graph::elevation(self.grid[&loc]) + 1)
-            .collect();
-        valid_neighbors
-    }
+/// Searches for the fewest steps from any of `starts` to the best signal.
+pub fn hike(graph: &Graph, starts: impl IntoIterator<Item = UPoint>) -> Search<UPoint, usize> {
+    search::bfs_multi(starts, |&v| graph.neighbors(v), |&v| v == graph.end_loc)
 }
 
-pub fn part_1(payload: &str) -> usize {
-    let graph: Graph = Graph::from(payload);
-    bfs(
-        &(graph.start_loc.unwrap()),
-        |v| graph.neighbors(*v),
-        |v| v == &(graph.end_loc.unwrap()),
-    )
-    .unwrap()
-    .len()
-        - 1
+pub fn part_1(graph: &Graph) -> Option<usize> {
+    hike(graph, [graph.start_loc]).cost()
 }
 
-pub fn part_2(payload: &str) -> usize {
-    let mut lengths: Vec<_> = vec![];
-    let graph: Graph = Graph::from(payload);
-    graph
-        .vertices()
-        .into_iter()
-        .filter(|x| Graph::elevation(graph.grid[x]) == 1)
-        .for_each(|start_node| {
-            let result = bfs(
-                &start_node,
-                |v| graph.neighbors(*v),
-                |v| v == &(graph.end_loc.unwrap()),
-            );
-            match result {
-                None => { /* destination unreachable from this start */ }
-                Some(path) => {
-                    lengths.push(path.len() - 1);
-                }
-            }
-        });
-    lengths.into_iter().min().unwrap()
+/// The fewest steps from any square at elevation `a`, searching from all of them at once.
+pub fn part_2(graph: &Graph) -> Option<usize> {
+    let lowest = graph.elevations.iter().filter(|&(_, &elevation)| elevation == 0);
+    hike(graph, lowest.map(|(start, _)| start)).cost()
 }
 
-pub fn run(payload: &str) {
-    let mut before = Instant::now();
-    let steps = part_1(payload);
-    println!(
-        "Day 12 - Part 1 (elapsed time: {:.2?}): {}",
-        before.elapsed(),
-        steps
-    );
-
-    before = Instant::now();
-    let steps_2 = part_2(payload);
-    println!(
-        "Day 12 - Part 2 (elapsed time: {:.2?}): {}",
-        before.elapsed(),
-        steps_2
-    );
+impl Solution for Day12 {
+    type Model = Graph;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        Graph::from(input)
+    }
+
+    fn part1(&self, graph: &Self::Model) -> Answer {
+        Answer::or_no_solution(part_1(graph), "E is unreachable from S")
+    }
+
+    fn part2(&self, graph: &Self::Model) -> Answer {
+        Answer::or_no_solution(part_2(graph), "E is unreachable from every a")
+    }
 }
 
 #[cfg(test)]
@@ -170,11 +97,40 @@ mod tests {
 
     #[test]
     fn find_least_steps() {
-        assert_eq!(part_1(SAMPLE), 31)
+        assert_eq!(part_1(&Graph::from(SAMPLE).unwrap()), Some(31))
     }
 
     #[test]
     fn hiking_exercise() {
-        assert_eq!(part_2(SAMPLE), 29)
+        assert_eq!(part_2(&Graph::from(SAMPLE).unwrap()), Some(29))
+    }
+
+    #[test]
+    fn unexpected_char() {
+        let error = Graph::from("Sab\nab?E\n").err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "?"));
+    }
+
+    #[test]
+    fn missing_end() {
+        assert!(Graph::from("Sabc\n").is_err());
+    }
+
+    #[test]
+    fn unreachable() {
+        let graph = Graph::from("Sbcz\naaaE\n").unwrap();
+        assert_eq!(part_1(&graph), None);
+        assert_eq!(part_2(&graph), None);
+    }
+
+    #[test]
+    fn path() {
+        let graph = Graph::from(SAMPLE).unwrap();
+        let path = hike(&graph, [graph.start_loc]).path().unwrap();
+        assert_eq!(path.len(), 32);
+        assert_eq!((path[0], path[31]), (graph.start_loc, graph.end_loc));
+        assert!(path
+            .windows(2)
+            .all(|step| graph.neighbors(step[0]).any(|next| next == step[1])));
     }
 }
@@ -1,29 +1,56 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
 use serde_json::{from_str, json, Value};
 use std::cmp::Ordering;
-use std::time::Instant;
+
+pub const DAY: Day = Day {
+    number: 13,
+    title: "Distress Signal",
+    solution: &Day13,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/13.txt"), "13"),
+        Sample::new(Part::Two, include_str!("samples/13.txt"), "140"),
+    ],
+};
+
+pub struct Day13;
 
 #[derive(Debug, Eq, PartialEq)]
-struct Pair {
+pub struct Pair {
     idx: usize,
     left: Value,
     right: Value,
 }
 
 impl Pair {
-    pub fn from_str(idx: usize, s: &str) -> Option<Pair> {
-        let mut lists = s.split('\n');
-        if let Some(list1) = lists.next() {
-            let list1: Value = from_str(list1).unwrap();
-            if let Some(list2) = lists.next() {
-                let list2: Value = from_str(list2).unwrap();
-                return Some(Pair {
-                    idx,
-                    left: list1,
-                    right: list2,
-                });
+    pub fn from_str(idx: usize, s: &str) -> Result<Pair, ParseError> {
+        let mut lists = s.lines().enumerate();
+        let mut packet = || {
+            let (i, line) = lists
+                .next()
+                .ok_or_else(|| ParseError::end_of(s, "expected a pair of packets"))?;
+            // serde_json counts columns in bytes.
+            let packet = from_str::<Value>(line).map_err(|e| {
+                ParseError::at(i + 1, line, e.column().saturating_sub(1), e.to_string())
+            })?;
+            match packet {
+                Value::Array(_) if is_packet_data(&packet) => Ok(packet),
+                _ => Err(ParseError::new(i + 1, 1, line, "expected a list of integers and lists")),
             }
-        }
-        None
+        };
+        Ok(Pair {
+            idx,
+            left: packet()?,
+            right: packet()?,
+        })
+    }
+}
+
+/// Whether `value` is a non-negative integer or a list of them and of such lists.
+fn is_packet_data(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => n.is_u64(),
+        Value::Array(items) => items.iter().all(is_packet_data),
+        _ => false,
     }
 }
 
@@ -51,13 +78,17 @@ fn compare(a: &Value, b: &Value) -> Option<Ordering> {
     }
 }
 
-pub fn part_1(payload: &str) -> usize {
+fn read_pairs(payload: &str) -> Result<Vec<Pair>, ParseError> {
+    let mut offset = 0;
     let mut pairs: Vec<Pair> = vec![];
     for (idx, pair_def) in payload.split("\n\n").enumerate() {
-        if let Some(p) = Pair::from_str(idx + 1, pair_def) {
-            pairs.push(p);
-        }
+        pairs.push(Pair::from_str(idx + 1, pair_def).map_err(|e| e.shifted(offset))?);
+        offset += pair_def.lines().count() + 1;
     }
+    Ok(pairs)
+}
+
+pub fn part_1(pairs: &[Pair]) -> usize {
     pairs
         .iter()
         .map(|p| compare(&p.left, &p.right))
@@ -66,51 +97,76 @@ pub fn part_1(payload: &str) -> usize {
         .map(|(i, _)| i + 1)
         .sum::<usize>()
 }
-pub fn part_2(payload: &str) -> usize {
-    let mut packets: Vec<Value> = payload
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|line| serde_json::from_str(line).unwrap())
+pub fn part_2(pairs: &[Pair]) -> usize {
+    let mut packets: Vec<Value> = pairs
+        .iter()
+        .flat_map(|p| [p.left.clone(), p.right.clone()])
         .collect();
     packets.extend([json!([[2]]), json!([[6]])]);
-    packets.sort_by(|a, b| compare(a, b).unwrap());
+    // Equal packets compare as `None`, which is fine to keep in either order.
+    packets.sort_by(|a, b| compare(a, b).unwrap_or(Ordering::Equal));
 
     let dp1 = packets.iter().position(|p| *p == json!([[2]])).unwrap() + 1;
     let dp2 = packets.iter().position(|p| *p == json!([[6]])).unwrap() + 1;
     dp1 * dp2
 }
 
-pub fn run(payload: &str) {
-    let mut before = Instant::now();
-    let steps = part_1(payload);
-    println!(
-        "Day 13 - Part 1 (elapsed time: {:.2?}): {}",
-        before.elapsed(),
-        steps
-    );
-
-    before = Instant::now();
-    let steps_2 = part_2(payload);
-    println!(
-        "Day 13 - Part 2 (elapsed time: {:.2?}): {}",
-        before.elapsed(),
-        steps_2
-    );
+impl Solution for Day13 {
+    type Model = Vec<Pair>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_pairs(input)
+    }
+
+    fn part1(&self, pairs: &Self::Model) -> Answer {
+        part_1(pairs).into()
+    }
+
+    fn part2(&self, pairs: &Self::Model) -> Answer {
+        part_2(pairs).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     const SAMPLE: &str = include_str!("samples/13.txt");
 
     #[test]
     fn part_one() {
-        assert_eq!(part_1(SAMPLE), 13)
+        assert_eq!(part_1(&read_pairs(SAMPLE).unwrap()), 13)
     }
 
     #[test]
     fn part_two() {
-        assert_eq!(part_2(SAMPLE), 140)
+        assert_eq!(part_2(&read_pairs(SAMPLE).unwrap()), 140)
+    }
+
+    #[test]
+    fn invalid_packet() {
+        let error = read_pairs("[1,1]\n[1]\n\n[[1],[2]\n[3]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 8));
+        let error = read_pairs("[1]\n[\"é\",x]\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 6, "x"));
+    }
+
+    #[rstest]
+    #[case("[1,-1]")]
+    #[case("[1.5]")]
+    #[case("[[\"a\"]]")]
+    #[case("{}")]
+    #[case("3")]
+    fn not_a_packet(#[case] packet: &str) {
+        let error = read_pairs(&format!("[1]\n{packet}\n")).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 1, packet));
+    }
+
+    #[test]
+    fn equal_packets() {
+        let pairs = read_pairs("[1]\n[1]\n").unwrap();
+        assert_eq!(part_1(&pairs), 0);
+        assert_eq!(part_2(&pairs), 12);
     }
 }
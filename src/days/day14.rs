@@ -0,0 +1,221 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use grid::Grid;
+use std::fmt;
+
+pub const DAY: Day = Day {
+    number: 14,
+    title: "Regolith Reservoir",
+    solution: &Day14,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/14.txt"), "24"),
+        Sample::new(Part::Two, include_str!("samples/14.txt"), "93"),
+    ],
+};
+
+/// Where the sand pours into the cave from.
+const SOURCE: (usize, usize) = (500, 0);
+
+pub struct Day14;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// A vertical slice of the cave, indexed by `[y][x - left]`.
+#[derive(Clone)]
+pub struct Cave {
+    tiles: Grid<Tile>,
+    /// The x coordinate of the first column of `tiles`.
+    left: usize,
+    /// The y coordinate of the lowest rock; the floor is two below it.
+    depth: usize,
+}
+
+type Path = Vec<(usize, usize)>;
+
+impl Cave {
+    fn new(paths: &[Path]) -> Self {
+        let points = || paths.iter().flatten();
+        let depth = points().map(|&(_, y)| y).max().unwrap_or(0);
+        // Sand piled up on the floor spreads out at most this far from the source.
+        let reach = depth + 2;
+        let left = points()
+            .map(|&(x, _)| x)
+            .min()
+            .unwrap_or(SOURCE.0)
+            .min(SOURCE.0.saturating_sub(reach));
+        let right = points()
+            .map(|&(x, _)| x)
+            .max()
+            .unwrap_or(SOURCE.0)
+            .max(SOURCE.0 + reach);
+
+        let mut tiles = Grid::init(depth + 2, right - left + 1, Tile::Air);
+        for path in paths {
+            for segment in path.windows(2) {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                for y in y1.min(y2)..=y1.max(y2) {
+                    for x in x1.min(x2)..=x1.max(x2) {
+                        tiles[y][x - left] = Tile::Rock;
+                    }
+                }
+            }
+        }
+        Cave { tiles, left, depth }
+    }
+
+    fn tile(&self, x: usize, y: usize) -> Tile {
+        self.tiles[y][x - self.left]
+    }
+
+    /// Drops one unit of sand from the source and returns where it comes to rest, or `None`
+    /// if it falls into the abyss or the source is already blocked.
+    fn drop_sand(&mut self, floor: bool) -> Option<(usize, usize)> {
+        let (mut x, mut y) = SOURCE;
+        if self.tile(x, y) != Tile::Air {
+            return None;
+        }
+        loop {
+            if !floor && y >= self.depth {
+                return None;
+            }
+            if y + 1 == self.tiles.rows() {
+                break;
+            }
+            match [Some(x), x.checked_sub(1), Some(x + 1)]
+                .into_iter()
+                .flatten()
+                .find(|&next| self.tile(next, y + 1) == Tile::Air)
+            {
+                Some(next) => (x, y) = (next, y + 1),
+                None => break,
+            }
+        }
+        self.tiles[y][x - self.left] = Tile::Sand;
+        Some((x, y))
+    }
+
+    /// Pours sand until no more comes to rest and returns how many units did.
+    pub fn fill(&mut self, floor: bool) -> usize {
+        std::iter::from_fn(|| self.drop_sand(floor)).count()
+    }
+}
+
+impl fmt::Display for Cave {
+    /// Draws the part of the cave holding rock or sand, like the puzzle description does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let filled: Vec<(usize, usize)> = (0..self.tiles.rows())
+            .flat_map(|y| (0..self.tiles.cols()).map(move |col| (col, y)))
+            .filter(|&(col, y)| self.tiles[y][col] != Tile::Air)
+            .map(|(col, y)| (col + self.left, y))
+            .chain([SOURCE])
+            .collect();
+        let min_x = filled.iter().map(|&(x, _)| x).min().unwrap();
+        let max_x = filled.iter().map(|&(x, _)| x).max().unwrap();
+        let max_y = filled.iter().map(|&(_, y)| y).max().unwrap();
+
+        for y in 0..=max_y {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in min_x..=max_x {
+                let c = match self.tile(x, y) {
+                    Tile::Rock => '#',
+                    Tile::Sand => 'o',
+                    Tile::Air if (x, y) == SOURCE => '+',
+                    Tile::Air => '.',
+                };
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_paths(payload: &str) -> Result<Vec<Path>, ParseError> {
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let path = line
+                .split(" -> ")
+                .map(|point| {
+                    let invalid = || ParseError::within(i + 1, line, point, "expected x,y");
+                    let (x, y) = point.split_once(',').ok_or_else(invalid)?;
+                    Ok((
+                        x.parse().map_err(|_| invalid())?,
+                        y.parse().map_err(|_| invalid())?,
+                    ))
+                })
+                .collect::<Result<Path, ParseError>>()?;
+            if let Some(segment) = path
+                .windows(2)
+                .find(|s| s[0].0 != s[1].0 && s[0].1 != s[1].1)
+            {
+                let message = format!("diagonal line from {:?} to {:?}", segment[0], segment[1]);
+                return Err(ParseError::new(i + 1, 1, line, message));
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+impl Solution for Day14 {
+    type Model = Cave;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        Ok(Cave::new(&read_paths(input)?))
+    }
+
+    fn part1(&self, cave: &Self::Model) -> Answer {
+        cave.clone().fill(false).into()
+    }
+
+    fn part2(&self, cave: &Self::Model) -> Answer {
+        cave.clone().fill(true).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/14.txt");
+
+    #[test]
+    fn fill_abyss() {
+        let mut cave = Cave::new(&read_paths(SAMPLE).unwrap());
+        assert_eq!(cave.fill(false), 24);
+        assert_eq!(
+            cave.to_string(),
+            concat!(
+                "......+...\n",
+                "..........\n",
+                "......o...\n",
+                ".....ooo..\n",
+                "....#ooo##\n",
+                "...o#ooo#.\n",
+                "..###ooo#.\n",
+                "....oooo#.\n",
+                ".o.ooooo#.\n",
+                "#########.",
+            )
+        );
+    }
+
+    #[test]
+    fn fill_floor() {
+        let mut cave = Cave::new(&read_paths(SAMPLE).unwrap());
+        assert_eq!(cave.fill(true), 93);
+        assert_eq!(cave.to_string().lines().next(), Some("..........o.........."));
+    }
+
+    #[test]
+    fn diagonal_line() {
+        let error = read_paths("498,4 -> 500,6\n").unwrap_err();
+        assert_eq!(error.line, 1);
+    }
+}
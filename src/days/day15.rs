@@ -0,0 +1,177 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use itertools::Itertools;
+use regex::Regex;
+
+/// The sample asks about a smaller area than the real input.
+const SAMPLE_AREA: Day15 = Day15 { row: 10, bound: 20 };
+
+pub const DAY: Day = Day {
+    number: 15,
+    title: "Beacon Exclusion Zone",
+    solution: &Day15 {
+        row: 2_000_000,
+        bound: 4_000_000,
+    },
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/15.txt"), "26").with_solution(&SAMPLE_AREA),
+        Sample::new(Part::Two, include_str!("samples/15.txt"), "56000011")
+            .with_solution(&SAMPLE_AREA),
+    ],
+};
+
+pub struct Day15 {
+    /// The row part 1 counts the positions that cannot hold a beacon in.
+    pub row: i64,
+    /// Part 2 searches for the distress beacon in `0..=bound` on both axes.
+    pub bound: i64,
+}
+
+pub type Position = (i64, i64);
+
+/// An inclusive range of x coordinates.
+type Interval = (i64, i64);
+
+pub struct Sensor {
+    position: Position,
+    beacon: Position,
+}
+
+impl Sensor {
+    fn radius(&self) -> i64 {
+        distance(self.position, self.beacon)
+    }
+
+    /// The x coordinates in `row` that are at least as close to the sensor as its beacon.
+    fn coverage(&self, row: i64) -> Option<Interval> {
+        let (x, y) = self.position;
+        let reach = self.radius() - (y - row).abs();
+        (reach >= 0).then_some((x - reach, x + reach))
+    }
+}
+
+fn distance(a: Position, b: Position) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// Merges overlapping and adjacent intervals into disjoint ones, sorted by start.
+fn merge(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort_unstable();
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn covered(sensors: &[Sensor], row: i64) -> Vec<Interval> {
+    merge(sensors.iter().filter_map(|s| s.coverage(row)).collect())
+}
+
+/// Counts the positions in `row` where the distress beacon cannot be.
+pub fn count_excluded(sensors: &[Sensor], row: i64) -> i64 {
+    let intervals = covered(sensors, row);
+    let beacons = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|&(x, y)| y == row && intervals.iter().any(|&(a, b)| (a..=b).contains(&x)))
+        .unique()
+        .count() as i64;
+    intervals.iter().map(|(a, b)| b - a + 1).sum::<i64>() - beacons
+}
+
+/// Finds the only position in `0..=bound` on both axes that no sensor covers.
+pub fn find_beacon(sensors: &[Sensor], bound: i64) -> Option<Position> {
+    (0..=bound).find_map(|row| {
+        let intervals = sensors
+            .iter()
+            .filter_map(|s| s.coverage(row))
+            .filter(|&(a, b)| b >= 0 && a <= bound)
+            .map(|(a, b)| (a.max(0), b.min(bound)))
+            .collect();
+        match merge(intervals)[..] {
+            [] => Some((0, row)),
+            [(start, _), ..] if start > 0 => Some((0, row)),
+            [(_, end), ..] if end < bound => Some((end + 1, row)),
+            _ => None,
+        }
+    })
+}
+
+pub fn tuning_frequency((x, y): Position) -> i64 {
+    x * 4_000_000 + y
+}
+
+fn read_sensors(payload: &str) -> Result<Vec<Sensor>, ParseError> {
+    let re = Regex::new(
+        r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$",
+    )
+    .unwrap();
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let invalid = || ParseError::new(i + 1, 1, line, "expected a sensor and its beacon");
+            let captures = re.captures(line).ok_or_else(invalid)?;
+            let coordinate = |group: usize| {
+                let m = captures.get(group).unwrap();
+                m.as_str()
+                    .parse()
+                    .map_err(|_| ParseError::within(i + 1, line, m.as_str(), "number out of range"))
+            };
+            Ok(Sensor {
+                position: (coordinate(1)?, coordinate(2)?),
+                beacon: (coordinate(3)?, coordinate(4)?),
+            })
+        })
+        .collect()
+}
+
+impl Solution for Day15 {
+    type Model = Vec<Sensor>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_sensors(input)
+    }
+
+    fn part1(&self, sensors: &Self::Model) -> Answer {
+        count_excluded(sensors, self.row).into()
+    }
+
+    fn part2(&self, sensors: &Self::Model) -> Answer {
+        let beacon = find_beacon(sensors, self.bound).map(tuning_frequency);
+        Answer::or_no_solution(beacon, "every position is covered by a sensor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/15.txt");
+
+    #[rstest]
+    #[case(vec![(1, 3), (5, 7)], vec![(1, 3), (5, 7)])]
+    #[case(vec![(5, 7), (1, 4)], vec![(1, 7)])]
+    #[case(vec![(1, 10), (2, 3), (4, 12)], vec![(1, 12)])]
+    #[case(vec![], vec![])]
+    fn merge_intervals(#[case] intervals: Vec<Interval>, #[case] expected: Vec<Interval>) {
+        assert_eq!(merge(intervals), expected)
+    }
+
+    #[test]
+    fn excluded_in_row() {
+        assert_eq!(count_excluded(&read_sensors(SAMPLE).unwrap(), 10), 26)
+    }
+
+    #[test]
+    fn distress_beacon() {
+        let sensors = read_sensors(SAMPLE).unwrap();
+        assert_eq!(find_beacon(&sensors, 20), Some((14, 11)));
+        assert_eq!(tuning_frequency((14, 11)), 56000011);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use pathfinding::prelude::dijkstra_all;
+use regex::Regex;
+use std::collections::HashMap;
+
+pub const DAY: Day = Day {
+    number: 16,
+    title: "Proboscidea Volcanium",
+    solution: &Day16,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/16.txt"), "1651"),
+        Sample::new(Part::Two, include_str!("samples/16.txt"), "1707"),
+    ],
+};
+
+const START: &str = "AA";
+/// The search keeps one entry per set of opened valves, so it needs 2^n of them.
+const MAX_VALVES: usize = 20;
+
+pub struct Day16;
+
+/// The valves worth opening, with the travel times between them.
+pub struct Network {
+    flows: Vec<u32>,
+    /// Minutes to walk from one valve to another; the start is the last index.
+    distances: Vec<Vec<u32>>,
+}
+
+impl Network {
+    fn new<'a>(valves: &HashMap<&'a str, (u32, Vec<&'a str>)>) -> Self {
+        let mut useful: Vec<&'a str> = valves
+            .iter()
+            .filter(|(_, (flow, _))| *flow > 0)
+            .map(|(&name, _)| name)
+            .collect();
+        useful.sort_unstable();
+        let reachable = |from: &'a str| {
+            dijkstra_all(&from, |&valve| valves[valve].1.iter().map(|&next| (next, 1)))
+        };
+        let from_start = reachable(START);
+        useful.retain(|name| from_start.contains_key(name));
+
+        let flows = useful.iter().map(|name| valves[name].0).collect();
+        let distances = useful
+            .iter()
+            .chain([&START])
+            .map(|&from| {
+                let paths = reachable(from);
+                useful
+                    .iter()
+                    .map(|to| paths.get(to).map_or(0, |&(_, cost)| cost))
+                    .collect()
+            })
+            .collect();
+        Network { flows, distances }
+    }
+
+    fn start(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// Walks every order of opening valves within `time` minutes, recording in `best` the
+    /// most pressure released for each set of opened valves.
+    fn explore(&self, at: usize, time: u32, opened: usize, released: u32, best: &mut [u32]) {
+        best[opened] = best[opened].max(released);
+        for (next, &flow) in self.flows.iter().enumerate() {
+            let cost = self.distances[at][next] + 1;
+            if opened & (1 << next) != 0 || cost >= time {
+                continue;
+            }
+            let remaining = time - cost;
+            let opened = opened | (1 << next);
+            self.explore(next, remaining, opened, released + remaining * flow, best);
+        }
+    }
+
+    /// The most pressure released in `time` minutes for each set of opened valves.
+    fn best_by_set(&self, time: u32) -> Vec<u32> {
+        let mut best = vec![0; 1 << self.flows.len()];
+        self.explore(self.start(), time, 0, 0, &mut best);
+        best
+    }
+
+    pub fn most_pressure(&self, time: u32) -> u32 {
+        self.best_by_set(time).into_iter().max().unwrap_or(0)
+    }
+
+    /// The most pressure released by two actors working in parallel, each opening its own
+    /// set of valves.
+    pub fn most_pressure_together(&self, time: u32) -> u32 {
+        let mut best = self.best_by_set(time);
+        // Let every set also stand for the best of its subsets, so that the split below only
+        // has to look at complementary sets.
+        for bit in 0..self.flows.len() {
+            for set in 0..best.len() {
+                if set & (1 << bit) != 0 {
+                    best[set] = best[set].max(best[set ^ (1 << bit)]);
+                }
+            }
+        }
+        let all = best.len() - 1;
+        (0..best.len())
+            .map(|set| best[set] + best[all ^ set])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn read_network(payload: &str) -> Result<Network, ParseError> {
+    let re = Regex::new(r"^Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (.+)$")
+        .unwrap();
+    let mut valves: HashMap<&str, (u32, Vec<&str>)> = HashMap::new();
+    for (i, line) in payload.lines().enumerate() {
+        let captures = re
+            .captures(line)
+            .ok_or_else(|| ParseError::new(i + 1, 1, line, "expected a valve and its tunnels"))?;
+        let name = captures.get(1).unwrap().as_str();
+        let flow = captures.get(2).unwrap().as_str();
+        let flow = flow
+            .parse()
+            .map_err(|_| ParseError::within(i + 1, line, flow, "flow rate out of range"))?;
+        let tunnels = captures.get(3).unwrap().as_str().split(", ").collect();
+        valves.insert(name, (flow, tunnels));
+    }
+
+    if !valves.contains_key(START) {
+        return Err(ParseError::end_of(payload, format!("no valve {START}")));
+    }
+    for (i, line) in payload.lines().enumerate() {
+        let tunnels = &valves[re.captures(line).unwrap().get(1).unwrap().as_str()].1;
+        if let Some(unknown) = tunnels.iter().find(|t| !valves.contains_key(*t)) {
+            return Err(ParseError::within(i + 1, line, unknown, "unknown valve"));
+        }
+    }
+    let network = Network::new(&valves);
+    if network.flows.len() > MAX_VALVES {
+        let message = format!("more than {MAX_VALVES} valves with a positive flow rate");
+        return Err(ParseError::end_of(payload, message));
+    }
+    Ok(network)
+}
+
+impl Solution for Day16 {
+    type Model = Network;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_network(input)
+    }
+
+    fn part1(&self, network: &Self::Model) -> Answer {
+        network.most_pressure(30).into()
+    }
+
+    fn part2(&self, network: &Self::Model) -> Answer {
+        network.most_pressure_together(26).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/16.txt");
+
+    #[test]
+    fn compressed() {
+        let network = read_network(SAMPLE).unwrap();
+        assert_eq!(network.flows, vec![13, 2, 20, 3, 22, 21]);
+        // From the start to BB, and from BB to HH.
+        assert_eq!(network.distances[network.start()][0], 1);
+        assert_eq!(network.distances[0][4], 6);
+    }
+
+    #[test]
+    fn alone() {
+        assert_eq!(read_network(SAMPLE).unwrap().most_pressure(30), 1651)
+    }
+
+    #[test]
+    fn with_elephant() {
+        assert_eq!(read_network(SAMPLE).unwrap().most_pressure_together(26), 1707)
+    }
+
+    #[test]
+    fn unknown_valve() {
+        let error = read_network("Valve AA has flow rate=0; tunnel leads to valve BB\n")
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (1, 49, "BB"));
+    }
+}
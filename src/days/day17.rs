@@ -0,0 +1,237 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use std::collections::HashMap;
+
+pub const DAY: Day = Day {
+    number: 17,
+    title: "Pyroclastic Flow",
+    solution: &Day17,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/17.txt"), "3068"),
+        Sample::new(Part::Two, include_str!("samples/17.txt"), "1514285714288"),
+    ],
+};
+
+const WIDTH: usize = 7;
+/// The rocks in the order they fall, one bit per cell from the bottom row up, as they
+/// appear: two cells from the left wall. The highest bit is the leftmost column.
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+
+pub struct Day17;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// The state the falling rocks repeat in: next rock, next jet and the shape of the top.
+type State = (usize, usize, [usize; WIDTH]);
+
+/// The tall, narrow chamber the rocks fall into, pushed around by the jets.
+pub struct Chamber<'a> {
+    jets: &'a [Jet],
+    /// Settled rock, one bit per cell from the floor up.
+    rows: Vec<u8>,
+    rocks: usize,
+    jet: usize,
+}
+
+impl<'a> Chamber<'a> {
+    pub fn new(jets: &'a [Jet]) -> Self {
+        Chamber {
+            jets,
+            rows: vec![],
+            rocks: 0,
+            jet: 0,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn collides(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter()
+            .zip(self.rows.iter().skip(bottom))
+            .any(|(r, row)| r & row != 0)
+    }
+
+    pub fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.rocks % ROCKS.len()].to_vec();
+        let mut bottom = self.height() + 3;
+        loop {
+            let pushed: Vec<u8> = match self.jets[self.jet] {
+                Jet::Left if rock.iter().all(|r| r & LEFT_WALL == 0) => {
+                    rock.iter().map(|r| r << 1).collect()
+                }
+                Jet::Right if rock.iter().all(|r| r & RIGHT_WALL == 0) => {
+                    rock.iter().map(|r| r >> 1).collect()
+                }
+                _ => rock.clone(),
+            };
+            self.jet = (self.jet + 1) % self.jets.len();
+            if !self.collides(&pushed, bottom) {
+                rock = pushed;
+            }
+            if bottom == 0 || self.collides(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+
+        for (i, r) in rock.into_iter().enumerate() {
+            match self.rows.get_mut(bottom + i) {
+                Some(row) => *row |= r,
+                None => self.rows.push(r),
+            }
+        }
+        self.rocks += 1;
+    }
+
+    /// How far below the top each column's highest rock is, or the height for an empty
+    /// column.
+    fn surface(&self) -> [usize; WIDTH] {
+        let mut surface = [self.height(); WIDTH];
+        for (column, depth) in surface.iter_mut().enumerate() {
+            let bit = LEFT_WALL >> column;
+            if let Some(d) = self.rows.iter().rev().position(|row| row & bit != 0) {
+                *depth = d;
+            }
+        }
+        surface
+    }
+
+    fn state(&self) -> State {
+        (self.rocks % ROCKS.len(), self.jet, self.surface())
+    }
+
+    /// The height of the tower after `rocks` rocks, skipping ahead once the falling rocks
+    /// start repeating themselves.
+    pub fn height_after(&mut self, rocks: usize) -> usize {
+        let mut seen: HashMap<State, (usize, usize)> = HashMap::new();
+        let mut skipped = 0;
+        while self.rocks < rocks {
+            self.drop_rock();
+            if skipped > 0 {
+                continue;
+            }
+            let now = (self.rocks, self.height());
+            if let Some((before_rocks, before_height)) = seen.insert(self.state(), now) {
+                let period = self.rocks - before_rocks;
+                let cycles = (rocks - self.rocks) / period;
+                self.rocks += cycles * period;
+                skipped = cycles * (self.height() - before_height);
+            }
+        }
+        self.height() + skipped
+    }
+
+    /// Draws the top `rows` rows of the chamber, and the floor if they reach it.
+    pub fn render(&self, rows: usize) -> String {
+        let mut lines: Vec<String> = self
+            .rows
+            .iter()
+            .rev()
+            .take(rows)
+            .map(|row| {
+                let cells: String = (0..WIDTH)
+                    .map(|column| match row & (LEFT_WALL >> column) {
+                        0 => '.',
+                        _ => '#',
+                    })
+                    .collect();
+                format!("|{cells}|")
+            })
+            .collect();
+        if rows > self.height() {
+            lines.push(format!("+{}+", "-".repeat(WIDTH)));
+        }
+        lines.join("\n")
+    }
+}
+
+fn read_jets(payload: &str) -> Result<Vec<Jet>, ParseError> {
+    let line = payload.trim_end();
+    if line.is_empty() {
+        return Err(ParseError::end_of(payload, "expected a jet pattern"));
+    }
+    line.char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(ParseError::at(1, line, i, "expected < or >")),
+        })
+        .collect()
+}
+
+impl Solution for Day17 {
+    type Model = Vec<Jet>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_jets(input)
+    }
+
+    fn part1(&self, jets: &Self::Model) -> Answer {
+        Chamber::new(jets).height_after(2022).into()
+    }
+
+    fn part2(&self, jets: &Self::Model) -> Answer {
+        Chamber::new(jets).height_after(1_000_000_000_000).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/17.txt");
+
+    #[rstest]
+    #[case(2022, 3068)]
+    #[case(1_000_000_000_000, 1514285714288)]
+    fn tower_height(#[case] rocks: usize, #[case] expected: usize) {
+        let jets = read_jets(SAMPLE).unwrap();
+        assert_eq!(Chamber::new(&jets).height_after(rocks), expected)
+    }
+
+    #[test]
+    fn render() {
+        let jets = read_jets(SAMPLE).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        (0..10).for_each(|_| chamber.drop_rock());
+        assert_eq!(
+            chamber.render(20),
+            concat!(
+                "|....#..|\n",
+                "|....#..|\n",
+                "|....##.|\n",
+                "|##..##.|\n",
+                "|######.|\n",
+                "|.###...|\n",
+                "|..#....|\n",
+                "|.####..|\n",
+                "|....##.|\n",
+                "|....##.|\n",
+                "|....#..|\n",
+                "|..#.#..|\n",
+                "|..#.#..|\n",
+                "|#####..|\n",
+                "|..###..|\n",
+                "|...#...|\n",
+                "|..####.|\n",
+                "+-------+",
+            )
+        );
+        assert_eq!(chamber.render(2), "|....#..|\n|....#..|");
+    }
+}
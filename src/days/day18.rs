@@ -0,0 +1,100 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use crate::voxel::{Voxel, VoxelSet};
+
+pub const DAY: Day = Day {
+    number: 18,
+    title: "Boiling Boulders",
+    solution: &Day18,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/18.txt"), "64"),
+        Sample::new(Part::Two, include_str!("samples/18.txt"), "58"),
+    ],
+};
+
+pub struct Day18;
+
+/// Counts the faces of the droplet that touch a voxel for which `open` holds.
+fn count_faces(droplet: &VoxelSet, open: impl Fn(Voxel) -> bool) -> usize {
+    droplet
+        .iter()
+        .flat_map(Voxel::neighbours)
+        .filter(|&v| open(v))
+        .count()
+}
+
+pub fn surface_area(droplet: &VoxelSet) -> usize {
+    count_faces(droplet, |v| !droplet.contains(v))
+}
+
+/// The surface area reachable by water and steam from outside the droplet.
+pub fn exterior_surface_area(droplet: &VoxelSet) -> usize {
+    let Some(bounds) = droplet.bounds() else {
+        return 0;
+    };
+    let bounds = bounds.grown(1);
+    let outside = droplet.flood_fill(bounds.min, bounds);
+    count_faces(droplet, |v| outside.contains(v))
+}
+
+fn read_droplet(payload: &str) -> Result<VoxelSet, ParseError> {
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let coordinates = line
+                .split(',')
+                .map(|c| {
+                    c.parse()
+                        .map_err(|_| ParseError::within(i + 1, line, c, "expected a coordinate"))
+                })
+                .collect::<Result<Vec<i32>, _>>()?;
+            match coordinates[..] {
+                [x, y, z] => Ok(Voxel::new(x, y, z)),
+                _ => Err(ParseError::new(i + 1, 1, line, "expected x,y,z")),
+            }
+        })
+        .collect()
+}
+
+impl Solution for Day18 {
+    type Model = VoxelSet;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_droplet(input)
+    }
+
+    fn part1(&self, droplet: &Self::Model) -> Answer {
+        surface_area(droplet).into()
+    }
+
+    fn part2(&self, droplet: &Self::Model) -> Answer {
+        exterior_surface_area(droplet).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/18.txt");
+
+    #[test]
+    fn two_cubes() {
+        let droplet = read_droplet("1,1,1\n2,1,1\n").unwrap();
+        assert_eq!(surface_area(&droplet), 10);
+        assert_eq!(exterior_surface_area(&droplet), 10);
+    }
+
+    #[test]
+    fn sample() {
+        let droplet = read_droplet(SAMPLE).unwrap();
+        assert_eq!(surface_area(&droplet), 64);
+        assert_eq!(exterior_surface_area(&droplet), 58);
+    }
+
+    #[test]
+    fn missing_coordinate() {
+        let error = read_droplet("1,1,1\n2,1\n").err().unwrap();
+        assert_eq!((error.line, error.text.as_str()), (2, "2,1"));
+    }
+}
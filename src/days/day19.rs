@@ -0,0 +1,222 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use regex::Regex;
+use std::str::FromStr;
+use std::thread;
+
+pub const DAY: Day = Day {
+    number: 19,
+    title: "Not Enough Minerals",
+    solution: &Day19,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/19.txt"), "33"),
+        Sample::new(Part::Two, include_str!("samples/19.txt"), "3472"),
+    ],
+};
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+pub struct Day19;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Blueprint {
+    id: u32,
+    /// What each kind of robot costs in ore, clay and obsidian.
+    costs: [[u32; 3]; 4],
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    time_left: u32,
+    robots: [u32; 4],
+    resources: [u32; 4],
+}
+
+impl FromStr for Blueprint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re = Regex::new(r"^Blueprint (?P<id>\d+):\s+Each ore robot costs (?P<ore_ore>\d+) ore\.\s+Each clay robot costs (?P<clay_ore>\d+) ore\.\s+Each obsidian robot costs (?P<obsidian_ore>\d+) ore and (?P<obsidian_clay>\d+) clay\.\s+Each geode robot costs (?P<geode_ore>\d+) ore and (?P<geode_obsidian>\d+) obsidian\.$").unwrap();
+        let captures = re
+            .captures(s)
+            .ok_or_else(|| ParseError::new(1, 1, s, "invalid blueprint"))?;
+        let number = |name: &str| {
+            let m = captures.name(name).unwrap();
+            m.as_str()
+                .parse()
+                .map_err(|_| ParseError::within(1, s, &s[m.range()], "number out of range"))
+        };
+
+        let mut costs = [[0; 3]; 4];
+        costs[ORE][ORE] = number("ore_ore")?;
+        costs[CLAY][ORE] = number("clay_ore")?;
+        costs[OBSIDIAN][ORE] = number("obsidian_ore")?;
+        costs[OBSIDIAN][CLAY] = number("obsidian_clay")?;
+        costs[GEODE][ORE] = number("geode_ore")?;
+        costs[GEODE][OBSIDIAN] = number("geode_obsidian")?;
+        Ok(Blueprint {
+            id: number("id")?,
+            costs,
+        })
+    }
+}
+
+impl Blueprint {
+    /// The most geodes the factory can open in `minutes`.
+    pub fn max_geodes(&self, minutes: u32) -> u32 {
+        // More robots of a kind than the most any robot costs of it can never be spent.
+        let mut max_robots = [u32::MAX; 4];
+        for (resource, max) in max_robots.iter_mut().take(GEODE).enumerate() {
+            *max = self.costs.iter().map(|c| c[resource]).max().unwrap();
+        }
+        let start = State {
+            time_left: minutes,
+            robots: [1, 0, 0, 0],
+            resources: [0; 4],
+        };
+        let mut best = 0;
+        self.search(start, &max_robots, &mut best);
+        best
+    }
+
+    /// Tries building each kind of robot next, waiting for the resources it needs.
+    fn search(&self, state: State, max_robots: &[u32; 4], best: &mut u32) {
+        let State {
+            time_left,
+            robots,
+            resources,
+        } = state;
+        let idle = resources[GEODE] + robots[GEODE] * time_left;
+        *best = (*best).max(idle);
+        // Even a new geode robot every remaining minute can't beat the best so far.
+        if idle + time_left * time_left.saturating_sub(1) / 2 <= *best {
+            return;
+        }
+
+        for robot in (ORE..=GEODE).rev() {
+            if robots[robot] >= max_robots[robot] {
+                continue;
+            }
+            let wait = (ORE..=OBSIDIAN).try_fold(0, |wait: u32, resource| {
+                let cost = self.costs[robot][resource];
+                if cost <= resources[resource] {
+                    Some(wait)
+                } else if robots[resource] == 0 {
+                    None
+                } else {
+                    let missing = cost - resources[resource];
+                    Some(wait.max(missing.div_ceil(robots[resource])))
+                }
+            });
+            let Some(wait) = wait else {
+                continue;
+            };
+            // A robot built in the last minute never gets to collect anything.
+            if wait + 1 >= time_left {
+                continue;
+            }
+
+            let mut next = State {
+                time_left: time_left - wait - 1,
+                robots,
+                resources,
+            };
+            for (resource, amount) in next.resources.iter_mut().enumerate() {
+                *amount += robots[resource] * (wait + 1);
+            }
+            for (resource, cost) in self.costs[robot].iter().enumerate() {
+                next.resources[resource] -= cost;
+            }
+            next.robots[robot] += 1;
+            self.search(next, max_robots, best);
+        }
+    }
+}
+
+/// The most geodes each blueprint can open, each evaluated on its own thread.
+pub fn max_geodes(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| scope.spawn(move || blueprint.max_geodes(minutes)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+fn read_blueprints(payload: &str) -> Result<Vec<Blueprint>, ParseError> {
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Blueprint::from_str(line).map_err(|e| e.shifted(i)))
+        .collect()
+}
+
+impl Solution for Day19 {
+    type Model = Vec<Blueprint>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_blueprints(input)
+    }
+
+    fn part1(&self, blueprints: &Self::Model) -> Answer {
+        max_geodes(blueprints, 24)
+            .iter()
+            .zip(blueprints)
+            .map(|(geodes, blueprint)| geodes * blueprint.id)
+            .sum::<u32>()
+            .into()
+    }
+
+    fn part2(&self, blueprints: &Self::Model) -> Answer {
+        let first = &blueprints[..blueprints.len().min(3)];
+        max_geodes(first, 32)
+            .iter()
+            .map(|&geodes| u64::from(geodes))
+            .product::<u64>()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use lazy_static::lazy_static;
+
+    const SAMPLE: &str = include_str!("samples/19.txt");
+
+    lazy_static! {
+        static ref BLUEPRINTS: Vec<Blueprint> = read_blueprints(SAMPLE).unwrap();
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            BLUEPRINTS[1],
+            Blueprint {
+                id: 2,
+                costs: [[2, 0, 0], [3, 0, 0], [3, 8, 0], [3, 0, 12]],
+            }
+        )
+    }
+
+    #[rstest]
+    #[case(0, 24, 9)]
+    #[case(1, 24, 12)]
+    #[case(0, 32, 56)]
+    #[case(1, 32, 62)]
+    fn most_geodes(#[case] blueprint: usize, #[case] minutes: u32, #[case] expected: u32) {
+        assert_eq!(BLUEPRINTS[blueprint].max_geodes(minutes), expected)
+    }
+
+    #[test]
+    fn invalid_cost() {
+        let error = read_blueprints(&SAMPLE.replace("costs 3 ore and 12", "costs x ore and 12"))
+            .unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+}
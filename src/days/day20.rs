@@ -0,0 +1,126 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+
+pub const DAY: Day = Day {
+    number: 20,
+    title: "Grove Positioning System",
+    solution: &Day20,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/20.txt"), "3"),
+        Sample::new(Part::Two, include_str!("samples/20.txt"), "1623178306"),
+    ],
+};
+
+pub const DECRYPTION_KEY: i64 = 811589153;
+
+pub struct Day20;
+
+/// Mixes the numbers `rounds` times, moving each by its value in the order they appear in
+/// `numbers`.
+pub fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    // Pair each number with its original index, so duplicates can be told apart.
+    let mut file: Vec<(usize, i64)> = numbers.iter().copied().enumerate().collect();
+    // Moving a number all the way around passes every other number once and returns it
+    // to where it was.
+    let cycle = numbers.len() as i64 - 1;
+    if cycle > 0 {
+        for _ in 0..rounds {
+            for original in 0..numbers.len() {
+                let from = file.iter().position(|&(i, _)| i == original).unwrap();
+                let entry = file.remove(from);
+                let to = (from as i64 + entry.1).rem_euclid(cycle) as usize;
+                file.insert(to, entry);
+            }
+        }
+    }
+    file.into_iter().map(|(_, n)| n).collect()
+}
+
+/// Sums the numbers 1000, 2000 and 3000 places after the 0, wrapping around.
+pub fn grove_coordinates(file: &[i64]) -> Option<i64> {
+    let zero = file.iter().position(|&n| n == 0)?;
+    Some(
+        [1000, 2000, 3000]
+            .iter()
+            .map(|offset| file[(zero + offset) % file.len()])
+            .sum(),
+    )
+}
+
+/// Multiplies the numbers by `key`, mixes them `rounds` times and finds the grove
+/// coordinates.
+pub fn decrypt(numbers: &[i64], key: i64, rounds: usize) -> Answer {
+    let numbers: Vec<i64> = numbers.iter().map(|n| n * key).collect();
+    Answer::or_no_solution(grove_coordinates(&mix(&numbers, rounds)), "no 0 in the file")
+}
+
+fn read_numbers(payload: &str) -> Result<Vec<i64>, ParseError> {
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|_| ParseError::new(i + 1, 1, line, "expected a number"))
+        })
+        .collect()
+}
+
+impl Solution for Day20 {
+    type Model = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_numbers(input)
+    }
+
+    fn part1(&self, numbers: &Self::Model) -> Answer {
+        decrypt(numbers, 1, 1)
+    }
+
+    fn part2(&self, numbers: &Self::Model) -> Answer {
+        decrypt(numbers, DECRYPTION_KEY, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/20.txt");
+
+    /// Rotates the file so that it starts at 0, as mixing leaves the start arbitrary.
+    fn from_zero(file: Vec<i64>) -> Vec<i64> {
+        let zero = file.iter().position(|&n| n == 0).unwrap();
+        file[zero..].iter().chain(&file[..zero]).copied().collect()
+    }
+
+    #[test]
+    fn mix_once() {
+        let file = mix(&read_numbers(SAMPLE).unwrap(), 1);
+        assert_eq!(from_zero(file.clone()), vec![0, 3, -2, 1, 2, -3, 4]);
+        assert_eq!(grove_coordinates(&file), Some(3));
+    }
+
+    #[test]
+    fn mix_with_key() {
+        let numbers: Vec<i64> = read_numbers(SAMPLE)
+            .unwrap()
+            .iter()
+            .map(|n| n * DECRYPTION_KEY)
+            .collect();
+        assert_eq!(grove_coordinates(&mix(&numbers, 10)), Some(1623178306));
+    }
+
+    #[test]
+    fn duplicates() {
+        // Both 1s move once each; looking them up by value would move the first one twice.
+        let file = mix(&[1, 1, 0], 1);
+        assert_eq!(from_zero(file), vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn no_zero() {
+        assert_eq!(
+            decrypt(&[1, 2], 1, 1),
+            Answer::NoSolution("no 0 in the file".into())
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use std::collections::HashMap;
+use std::fmt;
+
+pub const DAY: Day = Day {
+    number: 21,
+    title: "Monkey Math",
+    solution: &Day21,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/21.txt"), "152"),
+        Sample::new(Part::Two, include_str!("samples/21.txt"), "301"),
+    ],
+};
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+pub struct Day21;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Job {
+    Number(i64),
+    Operation(String, Operation, String),
+}
+
+/// Why a monkey's number can't be worked out exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MathError {
+    DivisionByZero,
+    /// The unknown is needed on both sides of the named monkey's operation.
+    BothSides(String),
+    /// Every value, or none, of the unknown satisfies the equation.
+    Indeterminate,
+    NotWhole(Ratio),
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::DivisionByZero => write!(f, "division by zero"),
+            MathError::BothSides(name) => write!(f, "{HUMAN} appears on both sides of {name}"),
+            MathError::Indeterminate => write!(f, "{HUMAN} is not determined by the equation"),
+            MathError::NotWhole(r) => write!(f, "{r} is not a whole number"),
+        }
+    }
+}
+
+impl std::error::Error for MathError {}
+
+/// An exact fraction in lowest terms, with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Ratio {
+    fn new(num: i128, den: i128) -> Result<Self, MathError> {
+        if den == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        let g = gcd(num, den) * den.signum();
+        Ok(Ratio {
+            num: num / g,
+            den: den / g,
+        })
+    }
+
+    fn apply(self, op: Operation, other: Ratio) -> Result<Ratio, MathError> {
+        let (a, b, c, d) = (self.num, self.den, other.num, other.den);
+        match op {
+            Operation::Add => Ratio::new(a * d + c * b, b * d),
+            Operation::Subtract => Ratio::new(a * d - c * b, b * d),
+            Operation::Multiply => Ratio::new(a * c, b * d),
+            Operation::Divide => Ratio::new(a * d, b * c),
+        }
+    }
+
+    fn whole(self) -> Result<i64, MathError> {
+        match self.den {
+            1 => i64::try_from(self.num).map_err(|_| MathError::NotWhole(self)),
+            _ => Err(MathError::NotWhole(self)),
+        }
+    }
+}
+
+impl From<i64> for Ratio {
+    fn from(n: i64) -> Self {
+        Ratio {
+            num: n.into(),
+            den: 1,
+        }
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            _ => write!(f, "{}/{}", self.num, self.den),
+        }
+    }
+}
+
+/// Every monkey's job by name. Operations only refer to monkeys that exist, without cycles.
+pub struct Jobs {
+    jobs: HashMap<String, Job>,
+}
+
+impl Jobs {
+    fn evaluate(&self, name: &str, memo: &mut HashMap<String, Ratio>) -> Result<Ratio, MathError> {
+        if let Some(&value) = memo.get(name) {
+            return Ok(value);
+        }
+        let value = match &self.jobs[name] {
+            Job::Number(n) => Ratio::from(*n),
+            Job::Operation(a, op, b) => {
+                let a = self.evaluate(a, memo)?;
+                a.apply(*op, self.evaluate(b, memo)?)?
+            }
+        };
+        memo.insert(name.to_string(), value);
+        Ok(value)
+    }
+
+    fn depends_on_human(&self, name: &str, memo: &mut HashMap<String, bool>) -> bool {
+        if let Some(&depends) = memo.get(name) {
+            return depends;
+        }
+        let depends = name == HUMAN
+            || match &self.jobs[name] {
+                Job::Number(_) => false,
+                Job::Operation(a, _, b) => {
+                    // Evaluate both, so that the memo covers every monkey on the way.
+                    let a = self.depends_on_human(a, memo);
+                    self.depends_on_human(b, memo) || a
+                }
+            };
+        memo.insert(name.to_string(), depends);
+        depends
+    }
+
+    /// The number `root` yells.
+    pub fn root(&self) -> Result<i64, MathError> {
+        self.evaluate(ROOT, &mut HashMap::new())?.whole()
+    }
+
+    /// The number to yell as `humn` so that both sides of `root` are equal.
+    pub fn human(&self) -> Result<i64, MathError> {
+        let Job::Operation(a, _, b) = &self.jobs[ROOT] else {
+            return Err(MathError::Indeterminate);
+        };
+        let mut depends = HashMap::new();
+        let mut values = HashMap::new();
+        let (unknown, known) = match (
+            self.depends_on_human(a, &mut depends),
+            self.depends_on_human(b, &mut depends),
+        ) {
+            (true, true) => return Err(MathError::BothSides(ROOT.to_string())),
+            (false, false) => return Err(MathError::Indeterminate),
+            (true, false) => (a, b),
+            (false, true) => (b, a),
+        };
+        let mut target = self.evaluate(known, &mut values)?;
+
+        // Walk down towards the unknown, undoing each operation on the way.
+        let mut name = unknown.as_str();
+        while name != HUMAN {
+            let Job::Operation(a, op, b) = &self.jobs[name] else {
+                unreachable!("only {HUMAN} itself depends on {HUMAN} without an operation");
+            };
+            let (next, other_on_left) = match (depends[a.as_str()], depends[b.as_str()]) {
+                (true, true) => return Err(MathError::BothSides(name.to_string())),
+                (true, false) => (a, false),
+                _ => (b, true),
+            };
+            let other = if other_on_left { a } else { b };
+            let other = self.evaluate(other, &mut values)?;
+            target = match (op, other_on_left) {
+                // x + o = t, o + x = t
+                (Operation::Add, _) => target.apply(Operation::Subtract, other)?,
+                // x * o = t, o * x = t
+                (Operation::Multiply, _) if other.num == 0 => return Err(MathError::Indeterminate),
+                (Operation::Multiply, _) => target.apply(Operation::Divide, other)?,
+                // x - o = t
+                (Operation::Subtract, false) => target.apply(Operation::Add, other)?,
+                // o - x = t
+                (Operation::Subtract, true) => other.apply(Operation::Subtract, target)?,
+                // x / o = t
+                (Operation::Divide, false) => target.apply(Operation::Multiply, other)?,
+                // o / x = t
+                (Operation::Divide, true) if target.num == 0 => {
+                    return Err(MathError::Indeterminate)
+                }
+                (Operation::Divide, true) => other.apply(Operation::Divide, target)?,
+            };
+            name = next;
+        }
+        target.whole()
+    }
+}
+
+fn read_jobs(payload: &str) -> Result<Jobs, ParseError> {
+    let mut jobs = HashMap::new();
+    let mut references = vec![];
+    for (i, line) in payload.lines().enumerate() {
+        let invalid = || ParseError::new(i + 1, 1, line, "expected a monkey and its job");
+        let (name, job) = line.split_once(": ").ok_or_else(invalid)?;
+        let job = match job.split(' ').collect::<Vec<_>>()[..] {
+            [number] => Job::Number(
+                number
+                    .parse()
+                    .map_err(|_| ParseError::within(i + 1, line, number, "expected a number"))?,
+            ),
+            [a, op, b] => {
+                let op = match op {
+                    "+" => Operation::Add,
+                    "-" => Operation::Subtract,
+                    "*" => Operation::Multiply,
+                    "/" => Operation::Divide,
+                    _ => return Err(ParseError::within(i + 1, line, op, "expected + - * or /")),
+                };
+                references.extend([(i, line, a), (i, line, b)]);
+                Job::Operation(a.to_string(), op, b.to_string())
+            }
+            _ => return Err(invalid()),
+        };
+        if jobs.insert(name.to_string(), job).is_some() {
+            return Err(ParseError::within(i + 1, line, name, "duplicate monkey"));
+        }
+    }
+
+    if let Some((i, line, name)) = references.iter().find(|(_, _, n)| !jobs.contains_key(*n)) {
+        return Err(ParseError::within(i + 1, line, name, "unknown monkey"));
+    }
+    if !jobs.contains_key(ROOT) {
+        return Err(ParseError::end_of(payload, format!("no monkey named {ROOT}")));
+    }
+    let jobs = Jobs { jobs };
+    if let Some(name) = find_cycle(&jobs) {
+        let (i, line) = payload
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.split_once(": ").is_some_and(|(n, _)| n == name))
+            .unwrap();
+        let name = &line[..name.len()];
+        return Err(ParseError::within(i + 1, line, name, "depends on itself"));
+    }
+    Ok(jobs)
+}
+
+/// Finds a monkey whose job ends up waiting on its own number.
+fn find_cycle(jobs: &Jobs) -> Option<&str> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Mark {
+        Visiting,
+        Done,
+    }
+
+    fn visit<'a>(jobs: &'a Jobs, name: &'a str, marks: &mut HashMap<&'a str, Mark>) -> bool {
+        match marks.get(name) {
+            Some(Mark::Visiting) => return true,
+            Some(Mark::Done) => return false,
+            None => {}
+        }
+        marks.insert(name, Mark::Visiting);
+        let cycle = match &jobs.jobs[name] {
+            Job::Number(_) => false,
+            Job::Operation(a, _, b) => visit(jobs, a, marks) || visit(jobs, b, marks),
+        };
+        marks.insert(name, Mark::Done);
+        cycle
+    }
+
+    let mut marks = HashMap::new();
+    jobs.jobs
+        .keys()
+        .map(String::as_str)
+        .find(|name| visit(jobs, name, &mut marks))
+}
+
+impl Solution for Day21 {
+    type Model = Jobs;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_jobs(input)
+    }
+
+    fn part1(&self, jobs: &Self::Model) -> Answer {
+        jobs.root()
+            .map_or_else(|e| Answer::NoSolution(e.to_string()), Answer::from)
+    }
+
+    fn part2(&self, jobs: &Self::Model) -> Answer {
+        if !jobs.jobs.contains_key(HUMAN) {
+            return Answer::NoSolution(format!("no monkey named {HUMAN}"));
+        }
+        jobs.human()
+            .map_or_else(|e| Answer::NoSolution(e.to_string()), Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/21.txt");
+
+    #[test]
+    fn root() {
+        assert_eq!(read_jobs(SAMPLE).unwrap().root(), Ok(152))
+    }
+
+    #[test]
+    fn human() {
+        assert_eq!(read_jobs(SAMPLE).unwrap().human(), Ok(301))
+    }
+
+    #[test]
+    fn exact_division() {
+        // 7 / 2 * 2 is 7 again, where integer division would give 6.
+        let jobs = read_jobs("root: half * two\nhalf: seven / two\nseven: 7\ntwo: 2\n").unwrap();
+        assert_eq!(jobs.root(), Ok(7));
+    }
+
+    #[test]
+    fn inverted_subtraction_and_division() {
+        // 12 / (10 - humn) = 3, so humn = 6.
+        let input = "root: ratio + three\nratio: twelve / diff\ndiff: ten - humn\n\
+                     twelve: 12\nten: 10\nthree: 3\nhumn: 0\n";
+        let jobs = read_jobs(input).unwrap();
+        assert_eq!(jobs.human(), Ok(6));
+    }
+
+    #[test]
+    fn both_sides() {
+        let jobs = read_jobs("root: a + b\na: humn * humn\nb: 4\nhumn: 1\n").unwrap();
+        assert_eq!(jobs.human(), Err(MathError::BothSides("a".into())));
+    }
+
+    #[test]
+    fn unknown_monkey() {
+        let error = read_jobs("root: a + b\na: 1\n").err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (1, 11, "b"));
+    }
+
+    #[test]
+    fn cycle() {
+        let error = read_jobs("root: a + b\na: b * c\nb: a - c\nc: 1\n").err().unwrap();
+        assert_eq!(error.message, "depends on itself");
+    }
+}
@@ -0,0 +1,452 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use std::collections::VecDeque;
+
+pub const DAY: Day = Day {
+    number: 22,
+    title: "Monkey Map",
+    solution: &Day22,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/22.txt"), "6032"),
+        Sample::new(Part::Two, include_str!("samples/22.txt"), "5031"),
+    ],
+};
+
+const VOID: u8 = b' ';
+const OPEN: u8 = b'.';
+const WALL: u8 = b'#';
+
+pub struct Day22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+/// Where on the board we are and which way we face: 0 is right, then clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    row: usize,
+    col: usize,
+    facing: usize,
+}
+
+impl Position {
+    fn password(&self) -> usize {
+        1000 * (self.row + 1) + 4 * (self.col + 1) + self.facing
+    }
+}
+
+pub struct Board {
+    tiles: Vec<Vec<u8>>,
+}
+
+impl Board {
+    fn tile(&self, row: isize, col: isize) -> u8 {
+        if row < 0 || col < 0 {
+            return VOID;
+        }
+        let row = self.tiles.get(row as usize);
+        row.and_then(|r| r.get(col as usize)).copied().unwrap_or(VOID)
+    }
+
+    /// The tile in front, if it is on the board.
+    fn ahead(&self, p: Position) -> Option<Position> {
+        let (dr, dc) = [(0, 1), (1, 0), (0, -1), (-1, 0)][p.facing];
+        let (row, col) = (p.row as isize + dr, p.col as isize + dc);
+        (self.tile(row, col) != VOID).then_some(Position {
+            row: row as usize,
+            col: col as usize,
+            ..p
+        })
+    }
+
+    fn start(&self) -> Position {
+        Position {
+            row: 0,
+            col: self.tiles[0].iter().position(|&t| t == OPEN).unwrap(),
+            facing: 0,
+        }
+    }
+
+    /// The next position, wrapping to the other end of the row or column off the edge.
+    fn step_flat(&self, p: Position) -> Position {
+        if let Some(next) = self.ahead(p) {
+            return next;
+        }
+        let back = Position {
+            facing: (p.facing + 2) % 4,
+            ..p
+        };
+        let edge = std::iter::successors(Some(back), |&q| self.ahead(q))
+            .last()
+            .unwrap();
+        Position {
+            facing: p.facing,
+            ..edge
+        }
+    }
+
+    /// Follows the path from the start, or returns `None` if `step` finds no next tile.
+    fn walk(
+        &self,
+        path: &[Step],
+        step: impl Fn(Position) -> Option<Position>,
+    ) -> Option<Position> {
+        let mut p = self.start();
+        for s in path {
+            match *s {
+                Step::Left => p.facing = (p.facing + 3) % 4,
+                Step::Right => p.facing = (p.facing + 1) % 4,
+                Step::Forward(n) => {
+                    for _ in 0..n {
+                        let next = step(p)?;
+                        if self.tile(next.row as isize, next.col as isize) == WALL {
+                            break;
+                        }
+                        p = next;
+                    }
+                }
+            }
+        }
+        Some(p)
+    }
+}
+
+type Vector = [i32; 3];
+
+fn neg(v: Vector) -> Vector {
+    v.map(|c| -c)
+}
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// One face of the folded cube and how it sits in space.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    /// The top left tile of the face on the board.
+    origin: (usize, usize),
+    /// Points out of the cube.
+    normal: Vector,
+    /// The direction of increasing columns and rows on the board.
+    right: Vector,
+    down: Vector,
+}
+
+impl Face {
+    /// The direction the facing points to in space.
+    fn direction(&self, facing: usize) -> Vector {
+        [self.right, self.down, neg(self.right), neg(self.down)][facing]
+    }
+}
+
+/// The board folded into a cube centred on the origin. Positions on it are the centres of
+/// the tiles, scaled by two to keep them whole.
+struct Cube<'a> {
+    board: &'a Board,
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl<'a> Cube<'a> {
+    /// Folds the board, which must be a net of six square faces.
+    fn fold(board: &'a Board) -> Option<Self> {
+        let tiles = board.tiles.iter().flatten().filter(|&&t| t != VOID).count();
+        let size = (1..=tiles).find(|s| 6 * s * s >= tiles)?;
+        if 6 * size * size != tiles {
+            return None;
+        }
+        let size_i = size as isize;
+        let is_face = |r: isize, c: isize| {
+            (0..size_i).all(|dr| {
+                (0..size_i).all(|dc| board.tile(r * size_i + dr, c * size_i + dc) != VOID)
+            })
+        };
+
+        // Fold each face away from the viewer, starting from the first one found.
+        let (first_row, first_col) = (0..board.tiles.len() / size)
+            .flat_map(|r| (0..=board.tiles[r * size].len() / size).map(move |c| (r, c)))
+            .find(|&(r, c)| is_face(r as isize, c as isize))?;
+        let mut faces = vec![Face {
+            origin: (first_row * size, first_col * size),
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }];
+        let mut todo = VecDeque::from([(first_row as isize, first_col as isize, faces[0])]);
+        while let Some((r, c, face)) = todo.pop_front() {
+            let Face {
+                normal: n,
+                right: x,
+                down: y,
+                ..
+            } = face;
+            for (dr, dc, normal, right, down) in [
+                (0, 1, x, neg(n), y),
+                (1, 0, y, x, neg(n)),
+                (0, -1, neg(x), n, y),
+                (-1, 0, neg(y), x, n),
+            ] {
+                let (r, c) = (r + dr, c + dc);
+                let origin = (r * size as isize, c * size as isize);
+                if !is_face(r, c) || faces.iter().any(|f| f.normal == normal) {
+                    continue;
+                }
+                let next = Face {
+                    origin: (origin.0 as usize, origin.1 as usize),
+                    normal,
+                    right,
+                    down,
+                };
+                faces.push(next);
+                todo.push_back((r, c, next));
+            }
+        }
+        (faces.len() == 6).then_some(Cube { board, size, faces })
+    }
+
+    /// The face the position is on, if any.
+    fn face(&self, p: Position) -> Option<&Face> {
+        self.faces.iter().find(|f| {
+            (f.origin.0..f.origin.0 + self.size).contains(&p.row)
+                && (f.origin.1..f.origin.1 + self.size).contains(&p.col)
+        })
+    }
+
+    /// The next position, continuing onto the neighbouring face of the cube off an edge, or
+    /// `None` if the position is not on a face.
+    fn step(&self, p: Position) -> Option<Position> {
+        if let Some(next) = self.board.ahead(p) {
+            return Some(next);
+        }
+        let n = self.size as i32;
+        let face = self.face(p)?;
+        // How far the tile is from the centre of the face, right and down.
+        let x = 2 * (p.col - face.origin.1) as i32 - (n - 1);
+        let y = 2 * (p.row - face.origin.0) as i32 - (n - 1);
+        let point: Vector =
+            std::array::from_fn(|k| n * face.normal[k] + x * face.right[k] + y * face.down[k]);
+
+        // Over the edge, the move turns from going along `heading` to going down `-normal`.
+        let heading = face.direction(p.facing);
+        let point: Vector = std::array::from_fn(|k| point[k] + heading[k] - face.normal[k]);
+        let next = self.faces.iter().find(|f| f.normal == heading)?;
+        let facing = (0..4).find(|&f| next.direction(f) == neg(face.normal))?;
+        Some(Position {
+            row: next.origin.0 + ((dot(point, next.down) + n - 1) / 2) as usize,
+            col: next.origin.1 + ((dot(point, next.right) + n - 1) / 2) as usize,
+            facing,
+        })
+    }
+}
+
+/// The board and the path to walk on it.
+pub struct Notes {
+    board: Board,
+    path: Vec<Step>,
+}
+
+impl Notes {
+    /// The password at the end of the path when leaving the board wraps around flat.
+    pub fn flat_password(&self) -> usize {
+        let board = &self.board;
+        let end = board.walk(&self.path, |p| Some(board.step_flat(p)));
+        end.expect("wrapping around flat always finds a tile").password()
+    }
+
+    /// The password at the end of the path when the board is folded into a cube, if it
+    /// folds.
+    pub fn cube_password(&self) -> Option<usize> {
+        let cube = Cube::fold(&self.board)?;
+        Some(self.board.walk(&self.path, |p| cube.step(p))?.password())
+    }
+}
+
+fn read_notes(payload: &str) -> Result<Notes, ParseError> {
+    let (map, path) = payload
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::end_of(payload, "expected a blank line before the path"))?;
+    let mut tiles = vec![];
+    for (i, line) in map.lines().enumerate() {
+        if let Some((offset, _)) = line
+            .char_indices()
+            .find(|&(_, c)| !c.is_ascii() || ![VOID, OPEN, WALL].contains(&(c as u8)))
+        {
+            return Err(ParseError::at(i + 1, line, offset, "expected ' ', '.' or '#'"));
+        }
+        tiles.push(line.as_bytes().to_vec());
+    }
+    if !tiles.first().is_some_and(|row| row.contains(&OPEN)) {
+        return Err(ParseError::new(1, 1, "", "no open tile to start on"));
+    }
+
+    let path_line = map.lines().count() + 2;
+    let path = path.trim_end();
+    let mut steps = vec![];
+    let mut rest = path;
+    while let Some(c) = rest.chars().next() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let (step, len) = match c {
+            'L' => (Step::Left, 1),
+            'R' => (Step::Right, 1),
+            _ => match rest[..digits].parse() {
+                Ok(n) => (Step::Forward(n), digits),
+                Err(_) => {
+                    let text = &rest[..c.len_utf8()];
+                    let message = "expected a number, L or R";
+                    return Err(ParseError::within(path_line, path, text, message));
+                }
+            },
+        };
+        steps.push(step);
+        rest = &rest[len..];
+    }
+    Ok(Notes {
+        board: Board { tiles },
+        path: steps,
+    })
+}
+
+impl Solution for Day22 {
+    type Model = Notes;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_notes(input)
+    }
+
+    fn part1(&self, notes: &Self::Model) -> Answer {
+        notes.flat_password().into()
+    }
+
+    fn part2(&self, notes: &Self::Model) -> Answer {
+        Answer::or_no_solution(
+            notes.cube_password(),
+            "the board doesn't fold into a cube",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/22.txt");
+
+    /// The eleven nets of a cube, one character per face.
+    const NETS: [&str; 11] = [
+        " #  \n####\n #  ",
+        "#   \n####\n#   ",
+        "#   \n####\n #  ",
+        "#   \n####\n  # ",
+        "#   \n####\n   #",
+        " #  \n####\n  # ",
+        "##  \n ###\n #  ",
+        "##  \n ###\n  # ",
+        "##  \n ###\n   #",
+        "##  \n ## \n  ##",
+        "###  \n  ###",
+    ];
+
+    /// Blows each face of a net up to a square of open tiles.
+    fn board(net: &str, size: usize) -> Board {
+        let tiles = net
+            .lines()
+            .flat_map(|line| {
+                let row: Vec<u8> = line
+                    .bytes()
+                    .flat_map(|f| vec![if f == b'#' { OPEN } else { VOID }; size])
+                    .collect();
+                vec![row; size]
+            })
+            .collect();
+        Board { tiles }
+    }
+
+    #[test]
+    fn flat() {
+        let notes = read_notes(SAMPLE).unwrap();
+        let end = notes.board.walk(&notes.path, |p| Some(notes.board.step_flat(p)));
+        let end = end.unwrap();
+        assert_eq!((end.row, end.col, end.facing), (5, 7, 0));
+    }
+
+    #[test]
+    fn cube() {
+        let notes = read_notes(SAMPLE).unwrap();
+        let cube = Cube::fold(&notes.board).unwrap();
+        let end = notes.board.walk(&notes.path, |p| cube.step(p)).unwrap();
+        assert_eq!((end.row, end.col, end.facing), (4, 6, 3));
+    }
+
+    fn assert_folds(net: &str) {
+        let size = 3;
+        let board = board(net, size);
+        let cube = Cube::fold(&board).unwrap();
+        for row in 0..board.tiles.len() {
+            for col in 0..board.tiles[row].len() {
+                if board.tiles[row][col] == VOID {
+                    continue;
+                }
+                for facing in 0..4 {
+                    let start = Position { row, col, facing };
+                    // Going straight around the cube leads back to where we started.
+                    let end = (0..4 * size).try_fold(start, |p, _| cube.step(p));
+                    assert_eq!(end, Some(start), "net\n{net}");
+                    // Turning around after a step leads back to the same tile.
+                    let next = cube.step(start).unwrap();
+                    let back = cube
+                        .step(Position {
+                            facing: (next.facing + 2) % 4,
+                            ..next
+                        })
+                        .unwrap();
+                    assert_eq!((back.row, back.col), (row, col), "net\n{net}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_net_folds() {
+        NETS.iter().for_each(|net| assert_folds(net));
+    }
+
+    #[test]
+    fn real_layout_folds() {
+        assert_folds(" ##\n # \n## \n#  ");
+    }
+
+    #[test]
+    fn not_a_cube() {
+        let notes = read_notes("....\n....\n\n1\n").unwrap();
+        assert!(Cube::fold(&notes.board).is_none());
+    }
+
+    #[test]
+    fn faces_off_the_grid() {
+        // The right number of tiles for a cube, but shifted off the blocks of its faces.
+        let mut board = board(NETS[0], 2);
+        board.tiles.iter_mut().for_each(|row| row.insert(0, VOID));
+        assert!(Cube::fold(&board).is_none());
+        let notes = Notes {
+            board,
+            path: vec![Step::Forward(10)],
+        };
+        assert_eq!(notes.cube_password(), None);
+    }
+
+    #[test]
+    fn non_ascii_tile() {
+        let error = read_notes("..é\n...\n\n10\n").err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (1, 3, "é"));
+    }
+
+    #[test]
+    fn non_ascii_step() {
+        let error = read_notes("...\n...\n\n10é\n").err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (4, 3, "é"));
+    }
+}
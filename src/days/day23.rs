@@ -0,0 +1,187 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+pub const DAY: Day = Day {
+    number: 23,
+    title: "Unstable Diffusion",
+    solution: &Day23,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/23.txt"), "110"),
+        Sample::new(Part::Two, include_str!("samples/23.txt"), "20"),
+    ],
+};
+
+/// The three positions an elf checks before proposing to move to the middle one, as
+/// `(row, col)` offsets, in the order the elves first consider them: north, south, west,
+/// east.
+const PROPOSALS: [[(i32, i32); 3]; 4] = [
+    [(-1, -1), (-1, 0), (-1, 1)],
+    [(1, -1), (1, 0), (1, 1)],
+    [(-1, -1), (0, -1), (1, -1)],
+    [(-1, 1), (0, 1), (1, 1)],
+];
+
+pub struct Day23;
+
+type Position = (i32, i32);
+
+#[derive(Clone)]
+pub struct Grove {
+    elves: HashSet<Position>,
+    /// How many rounds have been played.
+    rounds: usize,
+}
+
+impl Grove {
+    fn is_free(&self, (row, col): Position, offsets: &[(i32, i32)]) -> bool {
+        offsets
+            .iter()
+            .all(|(dr, dc)| !self.elves.contains(&(row + dr, col + dc)))
+    }
+
+    /// Plays one round and returns whether any elf moved.
+    pub fn round(&mut self) -> bool {
+        let around = PROPOSALS.concat();
+        let mut proposals: HashMap<Position, Vec<Position>> = HashMap::new();
+        for &elf in &self.elves {
+            if self.is_free(elf, &around) {
+                continue;
+            }
+            let proposal = (0..PROPOSALS.len())
+                .map(|i| PROPOSALS[(self.rounds + i) % PROPOSALS.len()])
+                .find(|offsets| self.is_free(elf, offsets));
+            if let Some([_, (dr, dc), _]) = proposal {
+                proposals
+                    .entry((elf.0 + dr, elf.1 + dc))
+                    .or_default()
+                    .push(elf);
+            }
+        }
+
+        let mut moved = false;
+        for (to, from) in proposals {
+            if let [elf] = from[..] {
+                self.elves.remove(&elf);
+                self.elves.insert(to);
+                moved = true;
+            }
+        }
+        self.rounds += 1;
+        moved
+    }
+
+    /// The smallest rectangle holding every elf, as its top left and bottom right corners.
+    fn bounds(&self) -> Option<(Position, Position)> {
+        let rows = self.elves.iter().map(|&(row, _)| row);
+        let cols = self.elves.iter().map(|&(_, col)| col);
+        Some((
+            (rows.clone().min()?, cols.clone().min()?),
+            (rows.max()?, cols.max()?),
+        ))
+    }
+
+    /// The empty tiles in the smallest rectangle holding every elf.
+    pub fn empty_ground(&self) -> usize {
+        self.bounds().map_or(0, |((top, left), (bottom, right))| {
+            ((bottom - top + 1) * (right - left + 1)) as usize - self.elves.len()
+        })
+    }
+}
+
+impl fmt::Display for Grove {
+    /// Draws the smallest rectangle holding every elf.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(((top, left), (bottom, right))) = self.bounds() else {
+            return Ok(());
+        };
+        for row in top..=bottom {
+            if row > top {
+                writeln!(f)?;
+            }
+            for col in left..=right {
+                let tile = if self.elves.contains(&(row, col)) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{tile}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_grove(payload: &str) -> Result<Grove, ParseError> {
+    let mut elves = HashSet::new();
+    for (row, line) in payload.lines().enumerate() {
+        for (col, (offset, c)) in line.char_indices().enumerate() {
+            match c {
+                '#' => {
+                    elves.insert((row as i32, col as i32));
+                }
+                '.' => {}
+                _ => return Err(ParseError::at(row + 1, line, offset, "expected # or .")),
+            }
+        }
+    }
+    Ok(Grove { elves, rounds: 0 })
+}
+
+impl Solution for Day23 {
+    type Model = Grove;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_grove(input)
+    }
+
+    fn part1(&self, grove: &Self::Model) -> Answer {
+        let mut grove = grove.clone();
+        (0..10).for_each(|_| {
+            grove.round();
+        });
+        grove.empty_ground().into()
+    }
+
+    fn part2(&self, grove: &Self::Model) -> Answer {
+        let mut grove = grove.clone();
+        while grove.round() {}
+        grove.rounds.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/23.txt");
+    const SMALL: &str = ".....\n..##.\n..#..\n.....\n..##.\n.....\n";
+
+    #[test]
+    fn small() {
+        let mut grove = read_grove(SMALL).unwrap();
+        assert_eq!(grove.to_string(), "##\n#.\n..\n##");
+        assert!(grove.round());
+        assert_eq!(grove.to_string(), "##\n..\n#.\n.#\n#.");
+        grove.round();
+        grove.round();
+        assert_eq!(grove.to_string(), "..#..\n....#\n#....\n....#\n.....\n..#..");
+        assert!(!grove.round());
+    }
+
+    #[test]
+    fn ten_rounds() {
+        let mut grove = read_grove(SAMPLE).unwrap();
+        (0..10).for_each(|_| {
+            grove.round();
+        });
+        assert_eq!(grove.empty_ground(), 110);
+    }
+
+    #[test]
+    fn no_elves() {
+        let mut grove = read_grove("...\n").unwrap();
+        assert_eq!(grove.empty_ground(), 0);
+        assert!(!grove.round());
+    }
+}
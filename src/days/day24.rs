@@ -0,0 +1,207 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use std::collections::{HashSet, VecDeque};
+
+pub const DAY: Day = Day {
+    number: 24,
+    title: "Blizzard Basin",
+    solution: &Day24,
+    samples: &[
+        Sample::new(Part::One, include_str!("samples/24.txt"), "18"),
+        Sample::new(Part::Two, include_str!("samples/24.txt"), "54"),
+    ],
+};
+
+/// `(row, col)` steps for staying put and moving in each direction.
+const MOVES: [(isize, isize); 5] = [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)];
+
+pub struct Day24;
+
+/// A position inside the walls, where the top left open tile is `(0, 0)`. The entrance
+/// and exit lie just outside.
+pub type Position = (isize, isize);
+
+pub struct Valley {
+    width: usize,
+    height: usize,
+    /// Where the blizzards of each direction start, indexed by `[row][col]`.
+    up: Vec<Vec<bool>>,
+    down: Vec<Vec<bool>>,
+    left: Vec<Vec<bool>>,
+    right: Vec<Vec<bool>>,
+    pub entrance: Position,
+    pub exit: Position,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Valley {
+    /// After this many minutes every blizzard is back where it started.
+    fn period(&self) -> usize {
+        self.width * self.height / gcd(self.width, self.height)
+    }
+
+    /// Whether a blizzard covers the tile at `time`, found by tracing back where each
+    /// blizzard that could be there started.
+    fn blizzard(&self, (row, col): (usize, usize), time: usize) -> bool {
+        let (w, h) = (self.width, self.height);
+        let (dx, dy) = (time % w, time % h);
+        self.right[row][(col + w - dx) % w]
+            || self.left[row][(col + dx) % w]
+            || self.down[(row + h - dy) % h][col]
+            || self.up[(row + dy) % h][col]
+    }
+
+    fn is_free(&self, (row, col): Position, time: usize) -> bool {
+        if (row, col) == self.entrance || (row, col) == self.exit {
+            return true;
+        }
+        let (Ok(row), Ok(col)) = (usize::try_from(row), usize::try_from(col)) else {
+            return false;
+        };
+        row < self.height && col < self.width && !self.blizzard((row, col), time)
+    }
+
+    /// The earliest time to reach `to` when leaving `from` at `start`, by breadth-first
+    /// search over positions and times in the blizzard period.
+    pub fn crossing(&self, from: Position, to: Position, start: usize) -> Option<usize> {
+        let period = self.period();
+        let mut seen = HashSet::from([(from, start % period)]);
+        let mut queue = VecDeque::from([(from, start)]);
+        while let Some(((row, col), time)) = queue.pop_front() {
+            if (row, col) == to {
+                return Some(time);
+            }
+            for (dr, dc) in MOVES {
+                let next = (row + dr, col + dc);
+                if self.is_free(next, time + 1) && seen.insert((next, (time + 1) % period)) {
+                    queue.push_back((next, time + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// The time to walk each leg in turn, starting at minute 0.
+    pub fn trip(&self, legs: &[(Position, Position)]) -> Option<usize> {
+        legs.iter()
+            .try_fold(0, |time, &(from, to)| self.crossing(from, to, time))
+    }
+}
+
+fn read_valley(payload: &str) -> Result<Valley, ParseError> {
+    let lines: Vec<&str> = payload.lines().collect();
+    let (Some(top), Some(bottom)) = (lines.first(), lines.last()) else {
+        return Err(ParseError::end_of(payload, "expected a valley"));
+    };
+    let width = top.chars().count().saturating_sub(2);
+    let height = lines.len().saturating_sub(2);
+    if width == 0 || height == 0 {
+        return Err(ParseError::end_of(payload, "expected a valley inside walls"));
+    }
+    let gap = |line: usize, text: &str| {
+        let gaps: Vec<usize> = text
+            .chars()
+            .enumerate()
+            .filter_map(|(col, tile)| (tile == '.').then_some(col))
+            .collect();
+        match gaps[..] {
+            [col] if text.chars().count() == width + 2 => Ok(col as isize - 1),
+            _ => Err(ParseError::new(line, 1, text, "expected a wall with one gap")),
+        }
+    };
+    let entrance = (-1, gap(1, top)?);
+    let exit = (height as isize, gap(lines.len(), bottom)?);
+
+    let mut valley = Valley {
+        width,
+        height,
+        up: vec![vec![false; width]; height],
+        down: vec![vec![false; width]; height],
+        left: vec![vec![false; width]; height],
+        right: vec![vec![false; width]; height],
+        entrance,
+        exit,
+    };
+    for (row, line) in lines[1..=height].iter().enumerate() {
+        let tiles: Vec<(usize, char)> = line.char_indices().collect();
+        if tiles.len() != width + 2 || tiles[0].1 != '#' || tiles[width + 1].1 != '#' {
+            return Err(ParseError::new(row + 2, 1, line, "expected a row between walls"));
+        }
+        for (col, &(offset, tile)) in tiles[1..=width].iter().enumerate() {
+            let blizzards = match tile {
+                '.' => continue,
+                '^' => &mut valley.up,
+                'v' => &mut valley.down,
+                '<' => &mut valley.left,
+                '>' => &mut valley.right,
+                _ => return Err(ParseError::at(row + 2, line, offset, "expected a blizzard")),
+            };
+            blizzards[row][col] = true;
+        }
+    }
+    Ok(valley)
+}
+
+impl Solution for Day24 {
+    type Model = Valley;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_valley(input)
+    }
+
+    fn part1(&self, valley: &Self::Model) -> Answer {
+        let time = valley.trip(&[(valley.entrance, valley.exit)]);
+        Answer::or_no_solution(time, "the blizzards block the way")
+    }
+
+    fn part2(&self, valley: &Self::Model) -> Answer {
+        let (there, back) = ((valley.entrance, valley.exit), (valley.exit, valley.entrance));
+        let time = valley.trip(&[there, back, there]);
+        Answer::or_no_solution(time, "the blizzards block the way")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("samples/24.txt");
+
+    #[test]
+    fn blizzards_move() {
+        // A single blizzard going right in a 5 wide valley, as in the puzzle text.
+        let valley = read_valley("#.#####\n#.....#\n#>....#\n#.....#\n#####.#\n").unwrap();
+        assert_eq!(valley.period(), 15);
+        assert!(valley.blizzard((1, 0), 0));
+        assert!(valley.blizzard((1, 3), 3));
+        assert!(valley.blizzard((1, 0), 5));
+        assert!(!valley.blizzard((1, 1), 5));
+    }
+
+    #[test]
+    fn crossings() {
+        let valley = read_valley(SAMPLE).unwrap();
+        let (entrance, exit) = (valley.entrance, valley.exit);
+        assert_eq!(valley.crossing(entrance, exit, 0), Some(18));
+        assert_eq!(valley.crossing(exit, entrance, 18), Some(41));
+        assert_eq!(valley.crossing(entrance, exit, 41), Some(54));
+    }
+
+    #[test]
+    fn invalid_blizzard() {
+        let error = read_valley(&SAMPLE.replace("#>v.><>#", "#>v.x<>#")).err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (4, 5, "x"));
+    }
+
+    #[test]
+    fn non_ascii_tile() {
+        let error = read_valley("#.###\n#.é.#\n###.#\n").err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "é"));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::days::{Answer, Day, ParseError, Part, Sample, Solution};
+use std::fmt;
+use std::iter::Sum;
+use std::num::TryFromIntError;
+use std::ops::Add;
+use std::str::FromStr;
+
+pub const DAY: Day = Day {
+    number: 25,
+    title: "Full of Hot Air",
+    solution: &Day25,
+    samples: &[Sample::new(Part::One, include_str!("samples/25.txt"), "2=-1=0")],
+};
+
+pub struct Day25;
+
+/// A number written in balanced base five, with the digits `=`, `-`, `0`, `1` and `2`
+/// standing for -2 to 2.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snafu(i128);
+
+impl Snafu {
+    fn digit(value: i128) -> char {
+        match value {
+            -2 => '=',
+            -1 => '-',
+            0 => '0',
+            1 => '1',
+            2 => '2',
+            _ => unreachable!("SNAFU digits range from -2 to 2"),
+        }
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new(1, 1, s, "expected a SNAFU number"));
+        }
+        let mut value: i128 = 0;
+        for (i, c) in s.char_indices() {
+            let digit = match c {
+                '=' => -2,
+                '-' => -1,
+                '0' => 0,
+                '1' => 1,
+                '2' => 2,
+                _ => return Err(ParseError::at(1, s, i, "expected a SNAFU digit")),
+            };
+            value = value
+                .checked_mul(5)
+                .and_then(|v| v.checked_add(digit))
+                .ok_or_else(|| ParseError::new(1, 1, s, "SNAFU number out of range"))?;
+        }
+        Ok(Snafu(value))
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return f.pad("0");
+        }
+        let mut digits = Vec::new();
+        let mut value = self.0;
+        while value != 0 {
+            // Take the remainder in -2..=2, carrying into the next digit when it is 3 or 4.
+            let (mut quotient, mut remainder) = (value.div_euclid(5), value.rem_euclid(5));
+            if remainder > 2 {
+                remainder -= 5;
+                quotient += 1;
+            }
+            digits.push(Snafu::digit(remainder));
+            value = quotient;
+        }
+        f.pad(&digits.iter().rev().collect::<String>())
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Snafu(value.into())
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(value: i128) -> Self {
+        Snafu(value)
+    }
+}
+
+impl From<Snafu> for i128 {
+    fn from(snafu: Snafu) -> Self {
+        snafu.0
+    }
+}
+
+impl TryFrom<Snafu> for i64 {
+    type Error = TryFromIntError;
+
+    fn try_from(snafu: Snafu) -> Result<Self, Self::Error> {
+        snafu.0.try_into()
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        Snafu(self.0 + other.0)
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Snafu {
+        iter.fold(Snafu::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Snafu {
+        iter.copied().sum()
+    }
+}
+
+fn read_numbers(payload: &str) -> Result<Vec<Snafu>, ParseError> {
+    payload
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse::<Snafu>().map_err(|e| e.shifted(i)))
+        .collect()
+}
+
+impl Solution for Day25 {
+    type Model = Vec<Snafu>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        read_numbers(input)
+    }
+
+    fn part1(&self, numbers: &Self::Model) -> Answer {
+        numbers.iter().sum::<Snafu>().to_string().into()
+    }
+
+    fn part2(&self, _: &Self::Model) -> Answer {
+        Answer::NoSolution("day 25 has no second part".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("samples/25.txt");
+
+    #[rstest]
+    #[case(0, "0")]
+    #[case(1, "1")]
+    #[case(2, "2")]
+    #[case(3, "1=")]
+    #[case(4, "1-")]
+    #[case(5, "10")]
+    #[case(6, "11")]
+    #[case(7, "12")]
+    #[case(8, "2=")]
+    #[case(9, "2-")]
+    #[case(10, "20")]
+    #[case(15, "1=0")]
+    #[case(20, "1-0")]
+    #[case(2022, "1=11-2")]
+    #[case(12345, "1-0---0")]
+    #[case(314159265, "1121-1110-1=0")]
+    // the input numbers
+    #[case(1747, "1=-0-2")]
+    #[case(906, "12111")]
+    #[case(198, "2=0=")]
+    #[case(11, "21")]
+    #[case(201, "2=01")]
+    #[case(31, "111")]
+    #[case(1257, "20012")]
+    #[case(32, "112")]
+    #[case(353, "1=-1=")]
+    #[case(107, "1-12")]
+    #[case(37, "122")]
+    // negative numbers
+    #[case(-1, "-")]
+    #[case(-3, "-2")]
+    #[case(-2022, "-2--1=")]
+    fn conversions(#[case] decimal: i64, #[case] snafu: &str) {
+        assert_eq!(Snafu::from(decimal).to_string(), snafu);
+        assert_eq!(i64::try_from(snafu.parse::<Snafu>().unwrap()), Ok(decimal));
+    }
+
+    #[test]
+    fn extremes() {
+        for value in [-i128::MAX, i128::MAX] {
+            let snafu = Snafu::from(value);
+            assert_eq!(snafu.to_string().parse(), Ok(snafu));
+        }
+        assert!(i64::try_from(Snafu::from(i128::MAX)).is_err());
+    }
+
+    #[test]
+    fn sum() {
+        let numbers = read_numbers(SAMPLE).unwrap();
+        let total: Snafu = numbers.iter().sum();
+        assert_eq!(i128::from(total), 4890);
+        assert_eq!(total.to_string(), "2=-1=0");
+        assert_eq!(Snafu::from(2i64) + Snafu::from(3i64), Snafu::from(5i64));
+    }
+
+    #[rstest]
+    #[case("1=3", 1, 3, "3")]
+    #[case("", 1, 1, "")]
+    #[case("22222222222222222222222222222222222222222222222222222222222", 1, 1, "2222")]
+    fn invalid(#[case] s: &str, #[case] line: usize, #[case] column: usize, #[case] text: &str) {
+        let error = s.parse::<Snafu>().unwrap_err();
+        assert_eq!((error.line, error.column), (line, column));
+        assert!(error.text.starts_with(text));
+    }
+
+    #[test]
+    fn invalid_line() {
+        let error = read_numbers("12\n1-x\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "x"));
+    }
+}
@@ -0,0 +1,527 @@
+use crate::days::ParseError;
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Index, IndexMut, Neg, Sub};
+
+/// A point on an unbounded 2D integer grid, with `y` growing downwards as in the puzzle
+/// maps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The number of king moves between the points.
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// The point with each coordinate replaced by its sign, i.e. a unit step towards it.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The four points sharing an edge with this one.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d.delta())
+    }
+
+    /// The eight points sharing an edge or a corner with this one.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL.into_iter().map(move |d| self + d.delta())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl From<UPoint> for Point {
+    fn from(p: UPoint) -> Self {
+        Point::new(p.x as i32, p.y as i32)
+    }
+}
+
+/// A point with unsigned coordinates, as used to index a [`Grid`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UPoint {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl UPoint {
+    pub const fn new(x: usize, y: usize) -> Self {
+        UPoint { x, y }
+    }
+
+    /// The point `delta` away, if it lies within `width` columns and `height` rows.
+    pub fn offset(self, delta: Point, width: usize, height: usize) -> Option<UPoint> {
+        let x = self.x.checked_add_signed(delta.x as isize)?;
+        let y = self.y.checked_add_signed(delta.y as isize)?;
+        (x < width && y < height).then_some(UPoint::new(x, y))
+    }
+
+    /// The up to four points sharing an edge with this one, within `width` by `height`.
+    pub fn neighbours(self, width: usize, height: usize) -> impl Iterator<Item = UPoint> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(d.delta(), width, height))
+    }
+
+    /// The up to eight points sharing an edge or a corner with this one, within `width` by
+    /// `height`.
+    pub fn neighbours8(self, width: usize, height: usize) -> impl Iterator<Item = UPoint> {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(d.delta(), width, height))
+    }
+}
+
+impl TryFrom<Point> for UPoint {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: Point) -> Result<Self, Self::Error> {
+        Ok(UPoint::new(p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+/// One of the four directions along the grid, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// A unit step in this direction.
+    pub const fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the eight compass directions, clockwise from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    /// A unit step in this direction, diagonally for the in-between ones.
+    pub const fn delta(self) -> Point {
+        match self {
+            Direction8::N => Point::new(0, -1),
+            Direction8::NE => Point::new(1, -1),
+            Direction8::E => Point::new(1, 0),
+            Direction8::SE => Point::new(1, 1),
+            Direction8::S => Point::new(0, 1),
+            Direction8::SW => Point::new(-1, 1),
+            Direction8::W => Point::new(-1, 0),
+            Direction8::NW => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::Up => Direction8::N,
+            Direction::Right => Direction8::E,
+            Direction::Down => Direction8::S,
+            Direction::Left => Direction8::W,
+        }
+    }
+}
+
+/// A rectangular grid storing a value for every cell, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a character map with one line per row, turning each character into a cell
+    /// with `cell`. Characters it rejects fail with `message`, as do rows of differing
+    /// widths.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        message: &str,
+    ) -> Result<Self, ParseError> {
+        let mut width = 0;
+        let mut cells = vec![];
+        for (i, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (offset, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| ParseError::at(i + 1, line, offset, message))?;
+                cells.push(value);
+            }
+            let row = cells.len() - before;
+            if i == 0 {
+                width = row;
+            } else if row != width {
+                let message = format!("expected {width} columns");
+                return Err(ParseError::new(i + 1, 1, line, message));
+            }
+        }
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        UPoint::try_from(p).is_ok_and(|p| p.x < self.width && p.y < self.height)
+    }
+
+    pub fn get(&self, p: UPoint) -> Option<&T> {
+        (p.x < self.width && p.y < self.height).then(|| &self.cells[p.y * self.width + p.x])
+    }
+
+    pub fn get_mut(&mut self, p: UPoint) -> Option<&mut T> {
+        (p.x < self.width && p.y < self.height).then(|| &mut self.cells[p.y * self.width + p.x])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = UPoint> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| UPoint::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (UPoint, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The first point, row by row, whose cell matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<UPoint> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The neighbouring point in `direction`, if it is on the grid.
+    pub fn step(&self, p: UPoint, direction: Direction) -> Option<UPoint> {
+        p.offset(direction.delta(), self.width, self.height)
+    }
+
+    /// The up to four points on the grid sharing an edge with `p`.
+    pub fn neighbours(&self, p: UPoint) -> impl Iterator<Item = UPoint> {
+        p.neighbours(self.width, self.height)
+    }
+
+    /// The up to eight points on the grid sharing an edge or a corner with `p`.
+    pub fn neighbours8(&self, p: UPoint) -> impl Iterator<Item = UPoint> {
+        p.neighbours8(self.width, self.height)
+    }
+
+    /// The points from `p`, exclusive, to the edge of the grid in `direction`.
+    pub fn ray(&self, p: UPoint, direction: Direction) -> impl Iterator<Item = UPoint> + '_ {
+        std::iter::successors(self.step(p, direction), move |&q| self.step(q, direction))
+    }
+
+    /// Draws the grid with one character per cell and one line per row.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        (0..self.height)
+            .map(|y| self.row(y).iter().map(&cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<UPoint> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: UPoint) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<UPoint> for Grid<T> {
+    fn index_mut(&mut self, p: UPoint) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the {width}x{height} grid"))
+    }
+}
+
+/// A grid storing values only for some points, without bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    /// Stores `value` at `p`, returning the value it replaces.
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    /// The stored points and values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle holding every stored
+    /// point, or `None` for an empty grid.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let first = *self.cells.keys().next()?;
+        Some(self.cells.keys().fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Draws the bounding rectangle with one character per cell, `None` standing for the
+    /// points without a value.
+    pub fn render(&self, cell: impl Fn(Option<&T>) -> char) -> String {
+        let Some((min, max)) = self.bounds() else {
+            return String::new();
+        };
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| cell(self.get(Point::new(x, y))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..\n.#.\n";
+
+    #[test]
+    fn directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        for d in Direction::ALL {
+            assert_eq!(d.opposite().delta(), -d.delta());
+            assert_eq!(Direction8::from(d).delta(), d.delta());
+        }
+        assert_eq!(Point::ORIGIN.neighbours8().count(), 8);
+    }
+
+    #[test]
+    fn bounded_neighbours() {
+        let corner: Vec<UPoint> = UPoint::new(0, 0).neighbours(3, 2).collect();
+        assert_eq!(corner, vec![UPoint::new(1, 0), UPoint::new(0, 1)]);
+        assert_eq!(UPoint::new(1, 1).neighbours8(3, 2).count(), 5);
+        assert_eq!(UPoint::new(2, 1).offset(Point::new(1, 0), 3, 2), None);
+    }
+
+    #[test]
+    fn parse_grid() {
+        let grid = Grid::parse(MAP, |c| (c == '#' || c == '.').then_some(c == '#'), "x").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[UPoint::new(1, 1)]);
+        assert_eq!(grid.get(UPoint::new(3, 0)), None);
+        assert!(grid.contains(Point::new(2, 1)));
+        assert!(!grid.contains(Point::new(-1, 0)));
+        assert_eq!(grid.position(|&wall| !wall), Some(UPoint::new(1, 0)));
+        assert_eq!(grid.column(1).filter(|&&wall| wall).count(), 1);
+        assert_eq!(
+            grid.render(|&wall| if wall { '#' } else { '.' }),
+            MAP.trim_end()
+        );
+
+        let ray: Vec<UPoint> = grid.ray(UPoint::new(0, 1), Direction::Right).collect();
+        assert_eq!(ray, vec![UPoint::new(1, 1), UPoint::new(2, 1)]);
+    }
+
+    #[test]
+    fn invalid_grid() {
+        let error = Grid::parse("ab\nc", Some, "").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected 2 columns")
+        );
+
+        let error = Grid::parse("..\n.x\n", |c| (c == '.').then_some(()), "expected .");
+        let error = error.unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+
+        assert_eq!(Grid::parse("", Some, "").unwrap().height(), 0);
+    }
+
+    #[test]
+    fn sparse_grid() {
+        let mut grid: SparseGrid<char> = [(Point::new(2, -1), 'a'), (Point::new(0, 1), 'b')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.bounds(), Some((Point::new(0, -1), Point::new(2, 1))));
+        assert_eq!(grid.render(|c| *c.unwrap_or(&'.')), "..a\n...\nb..");
+        assert_eq!(grid.insert(Point::new(0, 1), 'c'), Some('b'));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+    }
+}
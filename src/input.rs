@@ -0,0 +1,69 @@
+use crate::days::Day;
+use color_eyre::eyre::WrapErr;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+/// Directory holding the puzzle inputs, named `NN.txt` after their day, relative to the
+/// current directory.
+pub const INPUTS_DIR: &str = "src/days/inputs";
+/// Set to read the puzzle inputs from another directory than [`INPUTS_DIR`].
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+
+/// Where to read the puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The day's own input, read from [`inputs_dir`] or else embedded at compile time.
+    Default,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl From<&str> for Source {
+    fn from(s: &str) -> Self {
+        match s {
+            "-" => Source::Stdin,
+            path => Source::Path(path.into()),
+        }
+    }
+}
+
+/// The directory named by [`INPUTS_VAR`], or [`INPUTS_DIR`] if it isn't set.
+pub fn inputs_dir() -> PathBuf {
+    env::var_os(INPUTS_VAR).map_or_else(|| INPUTS_DIR.into(), PathBuf::from)
+}
+
+pub fn default_path(day: &Day) -> PathBuf {
+    inputs_dir().join(format!("{:02}.txt", day.number))
+}
+
+/// Whether the day has an input of its own, so it can run with [`Source::Default`].
+pub fn has_default(day: &Day) -> bool {
+    EMBEDDED_INPUTS.iter().any(|(d, _)| *d == day.number) || default_path(day).exists()
+}
+
+pub fn load(day: &Day, source: &Source) -> color_eyre::Result<String> {
+    match source {
+        Source::Default => {
+            let path = default_path(day);
+            match EMBEDDED_INPUTS.iter().find(|(d, _)| *d == day.number) {
+                Some((_, input)) if !path.exists() => Ok(input.to_string()),
+                _ => read_file(&path),
+            }
+        }
+        Source::Path(path) => read_file(path),
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .wrap_err("Failed to read input from stdin")?;
+            Ok(input)
+        }
+    }
+}
+
+fn read_file(path: &Path) -> color_eyre::Result<String> {
+    fs::read_to_string(path).wrap_err_with(|| format!("Failed to read input {}", path.display()))
+}
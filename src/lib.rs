@@ -0,0 +1,16 @@
+//! Advent of Code 2022 solutions.
+//!
+//! Every puzzle day lives in its own module under [`days`], exposing its parsed model and
+//! a solver implementing [`Solution`]. The [`runner`], [`bench`](mod@bench) and [`input`]
+//! modules drive the registered [`DAYS`] the same way the command line does.
+
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod geometry;
+pub mod input;
+pub mod runner;
+pub mod search;
+pub mod voxel;
+
+pub use days::{Answer, Day, ParseError, Part, Sample, Solution, Solver, DAYS};
@@ -1,29 +1,200 @@
-mod days;
+use aoc2022::input::{self, Source};
+use aoc2022::runner::{self, Format};
+use aoc2022::{answers, bench, Answer, Day, Part, DAYS};
+use color_eyre::eyre::{eyre, WrapErr};
+use std::num::NonZeroUsize;
+
+fn possible_days() -> String {
+    DAYS.iter()
+        .map(|d| format!("{:02}", d.number))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn find_day(day: &str) -> color_eyre::Result<&'static Day> {
+    day.parse().ok().and_then(Day::find).ok_or_else(|| {
+        eyre!(
+            "No valid day given. Possible options are: {}.",
+            possible_days()
+        )
+    })
+}
+
+/// Tells which days `all`, `verify` and `bench all` leave out for lack of an input.
+fn note_missing_inputs(answers: &answers::Answers) {
+    let missing: Vec<String> = DAYS
+        .iter()
+        .filter(|d| !runner::should_run(d, answers))
+        .map(|d| format!("{:02}", d.number))
+        .collect();
+    if !missing.is_empty() {
+        eprintln!("Skipped days without an input: {}", missing.join(", "));
+    }
+}
+
+fn print_outcome(outcome: &runner::Outcome) {
+    let day = outcome.day.number;
+    if let Some(elapsed) = outcome.parse_elapsed {
+        println!("Day {day:02} - Parse (elapsed time: {elapsed:.2?})");
+    }
+    let prefix = format!(
+        "Day {day:02} - Part {} (elapsed time: {:.2?})",
+        outcome.part, outcome.elapsed
+    );
+    match &outcome.answer {
+        Ok(Answer::Screen(screen)) => println!("{prefix}:\n{screen}"),
+        Ok(answer) => println!("{prefix}: {answer}"),
+        Err(_) => println!("{prefix}: FAILED"),
+    }
+}
+
+fn report(outcomes: &[runner::Outcome], format: Format) -> color_eyre::Result<()> {
+    match format {
+        Format::Json => runner::print_json(outcomes),
+        _ => runner::print_table(outcomes),
+    }
+    let failures = outcomes.iter().filter(|o| o.failed()).count();
+    if failures > 0 {
+        return Err(eyre!("{failures} of {} parts failed", outcomes.len()));
+    }
+    Ok(())
+}
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
-    let day: String = std::env::args()
-        .nth(1)
-        .expect("No day given. Possible options are: 01-25.");
-    let day_slice: &str = day.as_str();
-
-    match day_slice {
-        "01" => days::day01::run(include_str!("days/inputs/01.txt")),
-        "02" => days::day02::run(include_str!("days/inputs/02.txt")),
-        "03" => days::day03::run(include_str!("days/inputs/03.txt")),
-        "04" => days::day04::run(include_str!("days/inputs/04.txt")),
-        "05" => days::day05::run(include_str!("days/inputs/05.txt")),
-        "06" => days::day06::run(include_str!("days/inputs/06.txt")),
-        "07" => days::day07::run(include_str!("days/inputs/07.txt")),
-        "08" => days::day08::run(include_str!("days/inputs/08.txt")),
-        "09" => days::day09::run(include_str!("days/inputs/09.txt")),
-        "10" => days::day10::run(include_str!("days/inputs/10.txt")),
-        "11" => days::day11::run(include_str!("days/inputs/11.txt")),
-        "12" => days::day12::run(include_str!("days/inputs/12.txt")),
-        "13" => days::day13::run(include_str!("days/inputs/13.txt")),
-        _ => println!("No valid day given. Possible options are: 01-25."),
+    let mut source = Source::Default;
+    let mut sample = false;
+    let mut format = Format::Table;
+    let mut runs = None;
+    let mut args = vec![];
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path = argv
+                    .next()
+                    .ok_or_else(|| eyre!("--input needs a path, or - for stdin."))?;
+                source = Source::from(path.as_str());
+            }
+            "--sample" => sample = true,
+            "--format" => {
+                let value = argv
+                    .next()
+                    .ok_or_else(|| eyre!("--format needs a value."))?;
+                format = value.parse().map_err(|e: String| eyre!(e))?;
+            }
+            "--runs" => {
+                let value = argv.next().ok_or_else(|| eyre!("--runs needs a count."))?;
+                let count: usize = value
+                    .parse()
+                    .map_err(|_| eyre!("Invalid run count {value}."))?;
+                runs = Some(
+                    NonZeroUsize::new(count)
+                        .ok_or_else(|| eyre!("--runs needs a positive count."))?,
+                );
+            }
+            _ => args.push(arg),
+        }
+    }
+
+    let mut args = args.into_iter();
+    let day = args.next().ok_or_else(|| {
+        eyre!(
+            "No day given. Possible options are: list, all, verify, bench, {}.",
+            possible_days()
+        )
+    })?;
+
+    if day == "list" {
+        DAYS.iter().for_each(|d| println!("{d}"));
+        return Ok(());
+    }
+
+    if format == Format::Csv && day != "bench" {
+        return Err(eyre!("--format csv is only supported by bench."));
+    }
+
+    if day == "bench" {
+        let days: Vec<&Day> = match args.next().as_deref() {
+            Some("all") => {
+                if source != Source::Default {
+                    return Err(eyre!("--input can only be used with a single day."));
+                }
+                let answers = answers::Answers::load()?;
+                note_missing_inputs(&answers);
+                DAYS.iter()
+                    .filter(|d| runner::should_run(d, &answers))
+                    .collect()
+            }
+            Some(day) => vec![find_day(day)?],
+            None => return Err(eyre!("bench needs a day or all.")),
+        };
+        let mut measurements = vec![];
+        for day in days {
+            let input = input::load(day, &source)?;
+            let day_measurements = bench::bench(day, &input, runs)
+                .wrap_err_with(|| format!("Day {:02} has invalid input", day.number))?;
+            measurements.extend(day_measurements);
+        }
+        match format {
+            Format::Table => bench::print_table(&measurements),
+            Format::Csv => bench::print_csv(&measurements),
+            Format::Json => bench::print_json(&measurements),
+        }
+        return Ok(());
+    }
+
+    if day == "verify" {
+        let answers = answers::Answers::load()?;
+        note_missing_inputs(&answers);
+        let outcomes = runner::verify(&answers);
+        let unverified = outcomes.iter().filter(|o| o.expected.is_none()).count();
+        if unverified > 0 {
+            eprintln!("{unverified} parts have no accepted answer yet");
+        }
+        return report(&outcomes, format);
+    }
+
+    if day == "all" {
+        if source != Source::Default {
+            return Err(eyre!("--input can only be used with a single day."));
+        }
+        let outcomes = if sample {
+            DAYS.iter()
+                .flat_map(|day| runner::run_samples(day, &Part::BOTH))
+                .collect()
+        } else {
+            let answers = answers::Answers::load()?;
+            note_missing_inputs(&answers);
+            runner::run_all(&answers)
+        };
+        return report(&outcomes, format);
+    }
+
+    let day = find_day(&day)?;
+    let parts = match args.next() {
+        Some(part) => vec![part.parse().map_err(|e: String| eyre!(e))?],
+        None => Part::BOTH.to_vec(),
     };
 
-    Ok(())
+    if sample {
+        if source != Source::Default {
+            return Err(eyre!("--input and --sample can not be combined."));
+        }
+        return report(&runner::run_samples(day, &parts), format);
+    }
+
+    let input = input::load(day, &source)?;
+    let outcomes = runner::run(day, &parts, &input);
+    if format == Format::Json {
+        return report(&outcomes, format);
+    }
+    for outcome in &outcomes {
+        print_outcome(outcome);
+    }
+    match outcomes.iter().find_map(|o| o.answer.as_ref().err()) {
+        Some(message) => Err(eyre!("Day {:02} failed: {message}", day.number)),
+        None => Ok(()),
+    }
 }
@@ -0,0 +1,260 @@
+use crate::answers::Answers;
+use crate::days::{Answer, Day, Part, Solver};
+use crate::input::{self, Source};
+use serde_json::{json, Value};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+    /// One JSON object per line.
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "Invalid format {s}. Possible options are: table, csv, json."
+            )),
+        }
+    }
+}
+
+/// The result of running one part of a day, successful or not.
+pub struct Outcome {
+    pub day: &'static Day,
+    pub part: Part,
+    pub answer: Result<Answer, String>,
+    /// The answer this part should produce, if known.
+    pub expected: Option<String>,
+    /// Time spent parsing the input for this part, `None` if it reused the model of the
+    /// previous part.
+    pub parse_elapsed: Option<Duration>,
+    /// Time spent solving the part on the parsed model.
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    pub fn mismatch(&self) -> bool {
+        match (&self.answer, &self.expected) {
+            (Ok(answer), Some(expected)) => answer.to_string() != *expected,
+            _ => false,
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        self.answer.is_err() || self.mismatch()
+    }
+
+    fn error(day: &'static Day, part: Part, message: String) -> Self {
+        Outcome {
+            day,
+            part,
+            answer: Err(message),
+            expected: None,
+            parse_elapsed: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+/// Runs `f` and times it, turning a panic inside it into an error message.
+fn timed<T>(f: impl FnOnce() -> T) -> (Result<T, String>, Duration) {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let before = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let elapsed = before.elapsed();
+    panic::set_hook(hook);
+
+    let result = result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into())
+    });
+    (result, elapsed)
+}
+
+/// Parses the input once and solves each of the parts on the parsed model.
+pub fn run(day: &'static Day, parts: &[Part], input: &str) -> Vec<Outcome> {
+    run_with(day, day.solution, parts, input)
+}
+
+fn run_with(day: &'static Day, solution: &dyn Solver, parts: &[Part], input: &str) -> Vec<Outcome> {
+    let (model, parse_elapsed) = timed(|| solution.parse(input));
+    let model = model.and_then(|model| model.map_err(|e| format!("invalid input: {e}")));
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, &part)| {
+            let parse_elapsed = (i == 0).then_some(parse_elapsed);
+            match &model {
+                Ok(model) => {
+                    let (answer, elapsed) = timed(|| solution.solve_parsed(part, model.as_ref()));
+                    Outcome {
+                        parse_elapsed,
+                        elapsed,
+                        answer,
+                        ..Outcome::error(day, part, String::new())
+                    }
+                }
+                Err(message) => Outcome {
+                    parse_elapsed,
+                    ..Outcome::error(day, part, message.clone())
+                },
+            }
+        })
+        .collect()
+}
+
+/// Whether [`run_all`] runs `day`: it has an input of its own, or an accepted answer that
+/// a missing input must not silently pass.
+pub fn should_run(day: &Day, answers: &Answers) -> bool {
+    input::has_default(day) || answers.has_day(day)
+}
+
+/// Runs every day that [`should_run`], failing the days whose input can't be loaded.
+pub fn run_all(answers: &Answers) -> Vec<Outcome> {
+    crate::days::DAYS
+        .iter()
+        .filter(|day| should_run(day, answers))
+        .flat_map(|day| match input::load(day, &Source::Default) {
+            Ok(input) => run(day, &Part::BOTH, &input),
+            Err(e) => Part::BOTH
+                .map(|part| Outcome::error(day, part, format!("{e:#}")))
+                .into(),
+        })
+        .collect()
+}
+
+/// Runs every day and checks the answers against the accepted ones.
+pub fn verify(answers: &Answers) -> Vec<Outcome> {
+    run_all(answers)
+        .into_iter()
+        .map(|outcome| Outcome {
+            expected: answers.get(outcome.day, outcome.part).cloned(),
+            ..outcome
+        })
+        .collect()
+}
+
+/// Runs the day's samples for the given parts and checks them against their expected answers.
+pub fn run_samples(day: &'static Day, parts: &[Part]) -> Vec<Outcome> {
+    day.samples
+        .iter()
+        .filter(|sample| parts.contains(&sample.part))
+        .flat_map(|sample| {
+            run_with(day, sample.solver(day), &[sample.part], sample.input)
+                .into_iter()
+                .map(|outcome| Outcome {
+                    expected: Some(sample.expected.to_string()),
+                    ..outcome
+                })
+        })
+        .collect()
+}
+
+pub fn print_table(outcomes: &[Outcome]) {
+    let rows: Vec<(&Outcome, Vec<String>)> = outcomes
+        .iter()
+        .map(|o| {
+            let text = match (&o.answer, &o.expected) {
+                (Err(message), _) => format!("FAILED: {message}"),
+                (Ok(answer), Some(expected)) if o.mismatch() => {
+                    if expected.contains('\n') || matches!(answer, Answer::Screen(_)) {
+                        format!("{answer}\nexpected:\n{expected}")
+                    } else {
+                        format!("{answer} (expected {expected})")
+                    }
+                }
+                (Ok(answer), _) => answer.to_string(),
+            };
+            (o, text.lines().map(String::from).collect())
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .flat_map(|(_, lines)| lines.iter().map(|l| l.len()))
+        .chain(std::iter::once("Answer".len()))
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}",
+        "Day", "Part", "Answer", "Parse", "Solve"
+    );
+    for (outcome, lines) in &rows {
+        let parse_elapsed = outcome
+            .parse_elapsed
+            .map(|elapsed| format!("{elapsed:.2?}"))
+            .unwrap_or_default();
+        let elapsed = format!("{:.2?}", outcome.elapsed);
+        for (i, line) in lines.iter().enumerate() {
+            if i == 0 {
+                println!(
+                    "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}",
+                    format!("{:02}", outcome.day.number),
+                    outcome.part,
+                    line,
+                    parse_elapsed,
+                    elapsed
+                );
+            } else {
+                println!("{:>3}  {:>4}  {:<width$}", "", "", line);
+            }
+        }
+    }
+
+    let parse: Duration = outcomes.iter().filter_map(|o| o.parse_elapsed).sum();
+    let solve: Duration = outcomes.iter().map(|o| o.elapsed).sum();
+    let failures = outcomes.iter().filter(|o| o.failed()).count();
+    println!(
+        "Total time: {:.2?} (parse {parse:.2?}, solve {solve:.2?}), failures: {failures}",
+        parse + solve
+    );
+}
+
+fn answer_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(n) => json!(n),
+        Answer::Text(s) | Answer::Screen(s) => json!(s),
+        Answer::NoSolution(_) => Value::Null,
+    }
+}
+
+pub fn print_json(outcomes: &[Outcome]) {
+    for outcome in outcomes {
+        let (answer, kind, error) = match &outcome.answer {
+            Ok(answer) => (answer_json(answer), Some(answer.kind()), None),
+            Err(message) => (Value::Null, None, Some(message)),
+        };
+        println!(
+            "{}",
+            json!({
+                "day": outcome.day.number,
+                "part": outcome.part.number(),
+                "answer": answer,
+                "answer_type": kind,
+                "reason": match &outcome.answer {
+                    Ok(Answer::NoSolution(reason)) => Some(reason),
+                    _ => None,
+                },
+                "expected": outcome.expected,
+                "parse_ns": outcome.parse_elapsed.map(|elapsed| elapsed.as_nanos() as u64),
+                "elapsed_ns": outcome.elapsed.as_nanos() as u64,
+                "error": error,
+            })
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+use std::time::{Duration, Instant};
+
+/// A node of a search space. The searches clone states into their bookkeeping and look
+/// them up by value, and ask the caller for the successors of each state they expand.
+pub trait State: Clone + Eq + Hash {}
+
+impl<T: Clone + Eq + Hash> State for T {}
+
+/// The cost of a path in a weighted search, starting from `Default::default()` as zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// How much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States whose successors were generated.
+    pub expanded: usize,
+    /// The most states waiting in the queue at once.
+    pub max_frontier: usize,
+    pub elapsed: Duration,
+}
+
+/// The outcome of a search for a goal.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    /// The goal that was reached and the cost of the path to it.
+    pub goal: Option<(S, C)>,
+    pub stats: Stats,
+    /// The state each visited state was first or most cheaply reached from, `None` for the
+    /// starts.
+    parents: HashMap<S, Option<S>>,
+}
+
+impl<S: State, C: Copy> Search<S, C> {
+    pub fn cost(&self) -> Option<C> {
+        self.goal.as_ref().map(|&(_, cost)| cost)
+    }
+
+    /// The states from a start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        let (goal, _) = self.goal.as_ref()?;
+        let mut path: Vec<S> =
+            std::iter::successors(Some(goal), |&state| self.parents[state].as_ref())
+                .cloned()
+                .collect();
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// The distance from the nearest start to every reachable state.
+#[derive(Debug, Clone)]
+pub struct Flood<S> {
+    pub distances: HashMap<S, usize>,
+    pub stats: Stats,
+}
+
+/// Finds the fewest steps from `start` to a state satisfying `success`.
+pub fn bfs<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    success: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: State,
+    I: IntoIterator<Item = S>,
+{
+    bfs_multi([start], successors, success)
+}
+
+/// Finds the fewest steps from any of the `starts` to a state satisfying `success`.
+pub fn bfs_multi<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut success: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: State,
+    I: IntoIterator<Item = S>,
+{
+    let began = Instant::now();
+    let mut stats = Stats::default();
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if parents.insert(start.clone(), None).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+    stats.max_frontier = queue.len();
+    let mut goal = None;
+    while let Some((state, steps)) = queue.pop_front() {
+        if success(&state) {
+            goal = Some((state, steps));
+            break;
+        }
+        stats.expanded += 1;
+        for next in successors(&state) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(state.clone()));
+                queue.push_back((next, steps + 1));
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+    stats.elapsed = began.elapsed();
+    Search {
+        goal,
+        stats,
+        parents,
+    }
+}
+
+/// Finds the fewest steps from any of the `starts` to every state reachable from them.
+pub fn flood_fill<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> Flood<S>
+where
+    S: State,
+    I: IntoIterator<Item = S>,
+{
+    let began = Instant::now();
+    let mut stats = Stats::default();
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if distances.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+    stats.max_frontier = queue.len();
+    while let Some((state, steps)) = queue.pop_front() {
+        stats.expanded += 1;
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+    stats.elapsed = began.elapsed();
+    Flood { distances, stats }
+}
+
+/// Finds the cheapest path from `start` to a state satisfying `success`, where
+/// `successors` yields each next state with the cost of the step to it.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    success: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: State,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+/// Like [`dijkstra`], but expands the states in order of their cost so far plus
+/// `heuristic`, which must never overestimate the remaining cost to a goal.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut success: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: State,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let began = Instant::now();
+    let mut stats = Stats::default();
+    // The cheapest known cost to each state, along with where it was reached from.
+    let mut best: HashMap<S, (C, Option<S>)> =
+        HashMap::from([(start.clone(), (C::default(), None))]);
+    let mut frontier = BinaryHeap::from([Entry {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        state: start,
+    }]);
+    stats.max_frontier = frontier.len();
+    let mut goal = None;
+    while let Some(Entry { cost, state, .. }) = frontier.pop() {
+        if best[&state].0 < cost {
+            // Reached more cheaply since this entry was queued.
+            continue;
+        }
+        if success(&state) {
+            goal = Some((state, cost));
+            break;
+        }
+        stats.expanded += 1;
+        for (next, step) in successors(&state) {
+            let cost = cost + step;
+            if best.get(&next).is_some_and(|&(known, _)| known <= cost) {
+                continue;
+            }
+            best.insert(next.clone(), (cost, Some(state.clone())));
+            frontier.push(Entry {
+                estimate: cost + heuristic(&next),
+                cost,
+                state: next,
+            });
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    stats.elapsed = began.elapsed();
+    Search {
+        goal,
+        stats,
+        parents: best
+            .into_iter()
+            .map(|(state, (_, parent))| (state, parent))
+            .collect(),
+    }
+}
+
+/// A state waiting in the A* frontier, ordered so that the heap pops the lowest estimate
+/// first, and of equal estimates the one furthest along.
+struct Entry<S, C> {
+    estimate: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Grid, UPoint};
+
+    /// A maze with the start in the top left and the goal in the bottom right corner.
+    const MAZE: &str = concat!(
+        "..#....\n",
+        ".##.##.\n",
+        "....#..\n",
+        ".##...#\n",
+        "...#...\n",
+    );
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, |c| (c == '.' || c == '#').then_some(c == '.'), "").unwrap()
+    }
+
+    fn open(maze: &Grid<bool>, p: UPoint) -> impl Iterator<Item = UPoint> + '_ {
+        maze.neighbours(p).filter(|&q| maze[q])
+    }
+
+    #[test]
+    fn shortest_path() {
+        let maze = maze();
+        let goal = UPoint::new(6, 4);
+        let search = bfs(UPoint::new(0, 0), |&p| open(&maze, p), |&p| p == goal);
+        assert_eq!(search.cost(), Some(10));
+        let path = search.path().unwrap();
+        assert_eq!((path[0], path[10]), (UPoint::new(0, 0), goal));
+        assert!(path
+            .windows(2)
+            .all(|w| maze.neighbours(w[0]).any(|p| p == w[1])));
+        assert!(search.stats.expanded > 0);
+        assert!(search.stats.max_frontier > 0);
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let search = bfs(0, |&n| [n + 1], |&n| n == 0);
+        assert_eq!(search.path(), Some(vec![0]));
+        assert_eq!(search.stats.expanded, 0);
+        assert_eq!(search.stats.max_frontier, 1);
+    }
+
+    #[test]
+    fn frontier_counts_every_start() {
+        let search = bfs_multi([3, 1, 2], |&n| [n + 10], |&n| n == 1);
+        assert_eq!(search.stats.expanded, 1);
+        assert_eq!(search.stats.max_frontier, 3);
+        let flood = flood_fill([1, 2, 3], |_| []);
+        assert_eq!(flood.stats.max_frontier, 3);
+    }
+
+    #[test]
+    fn unreachable() {
+        let maze = maze();
+        let search = bfs(
+            UPoint::new(0, 0),
+            |&p| open(&maze, p),
+            |&p| p == UPoint::new(2, 0),
+        );
+        assert_eq!(search.cost(), None);
+        assert_eq!(search.path(), None);
+        // The goal is a wall, so every open cell was expanded looking for it.
+        assert_eq!(search.stats.expanded, 25);
+    }
+
+    #[test]
+    fn nearest_start() {
+        let maze = maze();
+        let starts = [UPoint::new(0, 0), UPoint::new(6, 0)];
+        let goal = UPoint::new(6, 4);
+        let search = bfs_multi(starts, |&p| open(&maze, p), |&p| p == goal);
+        assert_eq!(search.cost(), Some(6));
+        assert_eq!(search.path().unwrap()[0], UPoint::new(6, 0));
+    }
+
+    #[test]
+    fn all_distances() {
+        let maze = maze();
+        let flood = flood_fill([UPoint::new(0, 0)], |&p| open(&maze, p));
+        assert_eq!(flood.distances.len(), 25);
+        assert_eq!(flood.distances[&UPoint::new(6, 4)], 10);
+        assert_eq!(flood.stats.expanded, 25);
+    }
+
+    /// Steps from `n` to `n + 1` cost 3, jumps to `2 * n` cost 5.
+    fn steps_and_jumps(&n: &u32) -> [(u32, u32); 2] {
+        [(n + 1, 3), (2 * n, 5)]
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let search = dijkstra(1, steps_and_jumps, |&n| n == 20);
+        // 1 -> 2 -> 4 -> 5 -> 10 -> 20
+        assert_eq!(search.cost(), Some(3 + 5 + 3 + 5 + 5));
+        assert_eq!(search.path(), Some(vec![1, 2, 4, 5, 10, 20]));
+    }
+
+    #[test]
+    fn astar_expands_less() {
+        let maze = maze();
+        let goal = UPoint::new(6, 4);
+        let weighted = |&p: &UPoint| open(&maze, p).map(|q| (q, 1));
+        let plain = dijkstra(UPoint::new(0, 0), weighted, |&p| p == goal);
+        let distance = |p: &UPoint| goal.x.abs_diff(p.x) + goal.y.abs_diff(p.y);
+        let guided = astar(UPoint::new(0, 0), weighted, distance, |&p| p == goal);
+        assert_eq!(guided.cost(), Some(10));
+        assert_eq!(plain.cost(), guided.cost());
+        assert!(guided.stats.expanded < plain.stats.expanded);
+    }
+}
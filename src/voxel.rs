@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+/// A unit cube on a 3D integer grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Voxel {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Voxel {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Voxel { x, y, z }
+    }
+
+    /// The six voxels sharing a face with this one.
+    pub fn neighbours(self) -> impl Iterator<Item = Voxel> {
+        let Voxel { x, y, z } = self;
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .into_iter()
+        .map(move |(dx, dy, dz)| Voxel::new(x + dx, y + dy, z + dz))
+    }
+}
+
+/// The smallest box holding a set of voxels, bounds included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Voxel,
+    pub max: Voxel,
+}
+
+impl Bounds {
+    /// The bounds grown by `n` voxels on every side.
+    pub fn grown(self, n: i32) -> Self {
+        Bounds {
+            min: Voxel::new(self.min.x - n, self.min.y - n, self.min.z - n),
+            max: Voxel::new(self.max.x + n, self.max.y + n, self.max.z + n),
+        }
+    }
+
+    pub fn contains(&self, v: Voxel) -> bool {
+        (self.min.x..=self.max.x).contains(&v.x)
+            && (self.min.y..=self.max.y).contains(&v.y)
+            && (self.min.z..=self.max.z).contains(&v.z)
+    }
+}
+
+/// A sparse set of voxels.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoxelSet {
+    voxels: HashSet<Voxel>,
+}
+
+impl VoxelSet {
+    pub fn contains(&self, v: Voxel) -> bool {
+        self.voxels.contains(&v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Voxel> + '_ {
+        self.voxels.iter().copied()
+    }
+
+    /// The box around all voxels, or `None` for an empty set.
+    pub fn bounds(&self) -> Option<Bounds> {
+        let first = *self.voxels.iter().next()?;
+        Some(self.iter().fold(
+            Bounds {
+                min: first,
+                max: first,
+            },
+            |b, v| Bounds {
+                min: Voxel::new(b.min.x.min(v.x), b.min.y.min(v.y), b.min.z.min(v.z)),
+                max: Voxel::new(b.max.x.max(v.x), b.max.y.max(v.y), b.max.z.max(v.z)),
+            },
+        ))
+    }
+
+    /// The voxels outside this set that can be reached from `start` through face neighbours
+    /// without leaving `bounds`.
+    pub fn flood_fill(&self, start: Voxel, bounds: Bounds) -> VoxelSet {
+        let mut filled = VoxelSet::default();
+        let mut todo = vec![start];
+        while let Some(v) = todo.pop() {
+            if !bounds.contains(v) || self.contains(v) || !filled.voxels.insert(v) {
+                continue;
+            }
+            todo.extend(v.neighbours());
+        }
+        filled
+    }
+}
+
+impl FromIterator<Voxel> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Voxel>>(iter: I) -> Self {
+        VoxelSet {
+            voxels: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours() {
+        let around: VoxelSet = Voxel::new(0, 0, 0).neighbours().collect();
+        assert_eq!(around.iter().count(), 6);
+        assert!(around.contains(Voxel::new(0, 0, -1)));
+        assert!(!around.contains(Voxel::new(1, 1, 0)));
+    }
+
+    #[test]
+    fn bounds() {
+        let set: VoxelSet = [Voxel::new(1, -2, 3), Voxel::new(-1, 5, 0)]
+            .into_iter()
+            .collect();
+        let bounds = set.bounds().unwrap();
+        assert_eq!(bounds.min, Voxel::new(-1, -2, 0));
+        assert_eq!(bounds.max, Voxel::new(1, 5, 3));
+        assert_eq!(VoxelSet::default().bounds(), None);
+    }
+
+    #[test]
+    fn flood_fill_stays_outside() {
+        // A hollow 3x3x3 cube with its centre sealed off.
+        let shell: VoxelSet = (0..27)
+            .map(|i| Voxel::new(i % 3, i / 3 % 3, i / 9))
+            .filter(|&v| v != Voxel::new(1, 1, 1))
+            .collect();
+        let bounds = shell.bounds().unwrap().grown(1);
+        let outside = shell.flood_fill(bounds.min, bounds);
+        assert_eq!(outside.iter().count(), 125 - 27);
+        assert!(!outside.contains(Voxel::new(1, 1, 1)));
+    }
+}
@@ -0,0 +1,74 @@
+//! Uses the day models directly, the way other tools reuse the parsers and simulations.
+
+use aoc2022::days::{day07, day08, day10, day11, day12, day15, day25};
+use aoc2022::{Answer, Day, Solution};
+
+/// The input of the day's first sample.
+fn sample(number: u8) -> &'static str {
+    Day::find(number).unwrap().samples[0].input
+}
+
+#[test]
+fn filesystem() {
+    let filesystem = day07::Filesystem::new(sample(7)).unwrap();
+    assert_eq!(filesystem.get_size_over(100_000), 95437);
+    assert_eq!(filesystem.get_smallest_candidate(30_000_000), 24933642);
+}
+
+#[test]
+fn tree_patch() {
+    let patch = day08::TreePatch::new(sample(8)).unwrap();
+    assert_eq!(patch.count_visible_trees(), Some(21));
+    assert_eq!(patch.scenic_scores().into_iter().max(), Some(8));
+}
+
+#[test]
+fn cpu() {
+    let program = day10::Day10.parse(sample(10)).unwrap();
+    let cpu = day10::execute(&program);
+    assert_eq!(cpu.signal_strength, 13140);
+    assert!(cpu
+        .print_screen()
+        .starts_with("##..##..##..##..##..##..##..##..##..##.."));
+}
+
+#[test]
+fn monkeys() {
+    let mut monkeys: Vec<day11::Monkey> = day11::Day11.parse(sample(11)).unwrap();
+    assert_eq!(
+        day11::calc_monkey_business(&mut monkeys, 20, |x| x / 3),
+        10605
+    );
+}
+
+#[test]
+fn graph() {
+    let graph = day12::Graph::from(sample(12)).unwrap();
+    assert_eq!(day12::part_1(&graph), Some(31));
+    assert_eq!(day12::part_2(&graph), Some(29));
+}
+
+#[test]
+fn custom_parameters() {
+    let area = day15::Day15 { row: 10, bound: 20 };
+    let sensors = area.parse(sample(15)).unwrap();
+    assert_eq!(area.part1(&sensors), Answer::Number(26));
+    assert_eq!(day15::count_excluded(&sensors, 11), 28);
+}
+
+#[test]
+fn snafu() {
+    let total: day25::Snafu = ["1=", "2-", "1=11-2"]
+        .iter()
+        .map(|s| s.parse::<day25::Snafu>().unwrap())
+        .sum();
+    assert_eq!(i64::try_from(total), Ok(3 + 9 + 2022));
+    assert_eq!(total.to_string(), day25::Snafu::from(2034i64).to_string());
+}
+
+#[test]
+fn parse_errors() {
+    let error = day12::Graph::from("Sab\n?yE\n").err().unwrap();
+    assert_eq!((error.line, error.column), (2, 1));
+    assert!(error.to_string().starts_with("line 2, column 1: "));
+}
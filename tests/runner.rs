@@ -0,0 +1,55 @@
+use aoc2022::answers::Answers;
+use aoc2022::{runner, Answer, Day, Part, DAYS};
+
+#[test]
+fn every_sample_matches() {
+    for day in DAYS {
+        for outcome in runner::run_samples(day, &Part::BOTH) {
+            assert!(
+                !outcome.failed(),
+                "{} part {}: {:?}, expected {:?}",
+                day,
+                outcome.part,
+                outcome.answer,
+                outcome.expected
+            );
+        }
+    }
+}
+
+#[test]
+fn days_are_registered_in_order() {
+    let numbers: Vec<u8> = DAYS.iter().map(|d| d.number).collect();
+    assert_eq!(numbers, (1..=25).collect::<Vec<_>>());
+    assert_eq!(Day::find(7).unwrap().title, "No Space Left On Device");
+    assert!(Day::find(26).is_none());
+}
+
+#[test]
+fn invalid_input_fails_every_part() {
+    let day = Day::find(1).unwrap();
+    let outcomes = runner::run(day, &Part::BOTH, "1000\nlots\n");
+    assert_eq!(outcomes.len(), 2);
+    for outcome in outcomes {
+        let message = outcome.answer.unwrap_err();
+        assert!(
+            message.starts_with("invalid input: line 2, column 1"),
+            "{message}"
+        );
+    }
+}
+
+#[test]
+fn days_with_an_answer_run_without_an_input() {
+    let answers: Answers = "[25]\npart1 = \"2=-1=0\"\n".parse().unwrap();
+    assert!(runner::should_run(Day::find(25).unwrap(), &answers));
+    assert!(!runner::should_run(Day::find(24).unwrap(), &answers));
+}
+
+#[test]
+fn single_part() {
+    let day = Day::find(1).unwrap();
+    let outcomes = runner::run(day, &[Part::Two], "1\n\n2\n\n3\n\n4\n");
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].answer, Ok(Answer::Number(9)));
+}